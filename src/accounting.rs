@@ -1,31 +1,172 @@
-use crate::pool::DataKey;
+use crate::{interest::ReserveData, pool::{DelegationDataKey, DataKey, OracleConfig, PoolConfig, ReserveCaps, ReserveConfig, Session, SessionDataKey, UserReserveKey}};
 use soroban_auth::Identifier;
-use soroban_sdk::{Env};
+use soroban_sdk::{BytesN, Env, Vec};
 
-pub fn get_collateral(e: &Env, id: Identifier) -> i64 {
-    let key = DataKey::Collateral(id);
-    if let Some(balance) = e.data().get::<DataKey, i64>(key) {
+pub fn get_admin(e: &Env) -> Identifier {
+    e.data().get::<DataKey, Identifier>(DataKey::Admin).unwrap().unwrap()
+}
+
+pub fn set_admin(e: &Env, admin: &Identifier) {
+    e.data().set::<DataKey, Identifier>(DataKey::Admin, admin.clone());
+}
+
+pub fn get_collateral(e: &Env, token: &BytesN<32>, id: Identifier) -> i128 {
+    let key = DataKey::Collateral(UserReserveKey { token: token.clone(), id });
+    if let Some(balance) = e.data().get::<DataKey, i128>(key) {
         balance.unwrap()
     } else {
         0
     }
 }
 
-pub fn set_collateral(e: &Env, id: Identifier, amount: i64) {
-    let key = DataKey::Collateral(id);
-    e.data().set::<DataKey, i64>(key, amount);
+pub fn set_collateral(e: &Env, token: &BytesN<32>, id: Identifier, amount: i128) {
+    let key = DataKey::Collateral(UserReserveKey { token: token.clone(), id });
+    e.data().set::<DataKey, i128>(key, amount);
 }
 
-pub fn get_liabilities(e: &Env, id: Identifier) -> i64 {
-    let key = DataKey::Liability(id);
-    if let Some(balance) = e.data().get::<DataKey, i64>(key) {
+pub fn get_liabilities(e: &Env, token: &BytesN<32>, id: Identifier) -> i128 {
+    let key = DataKey::Liability(UserReserveKey { token: token.clone(), id });
+    if let Some(balance) = e.data().get::<DataKey, i128>(key) {
         balance.unwrap()
     } else {
         0
     }
 }
 
-pub fn set_liabilities(e: &Env, id: Identifier, amount: i64) {
-    let key = DataKey::Liability(id);
-    e.data().set::<DataKey, i64>(key, amount);
-}
\ No newline at end of file
+pub fn set_liabilities(e: &Env, token: &BytesN<32>, id: Identifier, amount: i128) {
+    let key = DataKey::Liability(UserReserveKey { token: token.clone(), id });
+    e.data().set::<DataKey, i128>(key, amount);
+}
+
+pub fn get_borrow_allowance(e: &Env, token: &BytesN<32>, delegator: Identifier, delegatee: Identifier) -> i128 {
+    let key = DataKey::BorrowAllowance(DelegationDataKey { token: token.clone(), delegator, delegatee });
+    if let Some(allowance) = e.data().get::<DataKey, i128>(key) {
+        allowance.unwrap()
+    } else {
+        0
+    }
+}
+
+pub fn set_borrow_allowance(e: &Env, token: &BytesN<32>, delegator: Identifier, delegatee: Identifier, amount: i128) {
+    let key = DataKey::BorrowAllowance(DelegationDataKey { token: token.clone(), delegator, delegatee });
+    e.data().set::<DataKey, i128>(key, amount);
+}
+
+pub fn get_delegated_liabilities(e: &Env, token: &BytesN<32>, delegator: Identifier, delegatee: Identifier) -> i128 {
+    let key = DataKey::DelegatedLiability(DelegationDataKey { token: token.clone(), delegator, delegatee });
+    if let Some(balance) = e.data().get::<DataKey, i128>(key) {
+        balance.unwrap()
+    } else {
+        0
+    }
+}
+
+/// Set the liabilities of a reserve the delegatee owes the delegator, keeping the delegator's
+/// list of delegatees with outstanding liabilities of that reserve in sync
+pub fn set_delegated_liabilities(e: &Env, token: &BytesN<32>, delegator: Identifier, delegatee: Identifier, amount: i128) {
+    let delegatees = get_delegatees(e, token, delegator.clone());
+    let mut new_delegatees: Vec<Identifier> = Vec::new(e);
+    for id in delegatees.iter() {
+        let id = id.unwrap();
+        if id != delegatee {
+            new_delegatees.push_back(id);
+        }
+    }
+    if amount != 0 {
+        new_delegatees.push_back(delegatee.clone());
+    }
+    let list_key = DataKey::Delegatees(UserReserveKey { token: token.clone(), id: delegator.clone() });
+    e.data().set::<DataKey, Vec<Identifier>>(list_key, new_delegatees);
+
+    let key = DataKey::DelegatedLiability(DelegationDataKey { token: token.clone(), delegator, delegatee });
+    e.data().set::<DataKey, i128>(key, amount);
+}
+
+pub fn get_delegatees(e: &Env, token: &BytesN<32>, delegator: Identifier) -> Vec<Identifier> {
+    let key = DataKey::Delegatees(UserReserveKey { token: token.clone(), id: delegator });
+    if let Some(delegatees) = e.data().get::<DataKey, Vec<Identifier>>(key) {
+        delegatees.unwrap()
+    } else {
+        Vec::new(e)
+    }
+}
+
+pub fn get_reserves(e: &Env) -> Vec<BytesN<32>> {
+    e.data().get::<DataKey, Vec<BytesN<32>>>(DataKey::Reserves).unwrap().unwrap()
+}
+
+pub fn set_reserves(e: &Env, reserves: &Vec<BytesN<32>>) {
+    e.data().set::<DataKey, Vec<BytesN<32>>>(DataKey::Reserves, reserves.clone());
+}
+
+pub fn get_reserve_config(e: &Env, token: &BytesN<32>) -> ReserveConfig {
+    e.data().get::<DataKey, ReserveConfig>(DataKey::ResConfig(token.clone())).unwrap().unwrap()
+}
+
+pub fn set_reserve_config(e: &Env, token: &BytesN<32>, config: &ReserveConfig) {
+    e.data().set::<DataKey, ReserveConfig>(DataKey::ResConfig(token.clone()), config.clone());
+}
+
+pub fn get_reserve_data(e: &Env, token: &BytesN<32>) -> ReserveData {
+    e.data().get::<DataKey, ReserveData>(DataKey::Reserve(token.clone())).unwrap().unwrap()
+}
+
+pub fn set_reserve_data(e: &Env, token: &BytesN<32>, data: &ReserveData) {
+    e.data().set::<DataKey, ReserveData>(DataKey::Reserve(token.clone()), data.clone());
+}
+
+/// Get the caps of a reserve, leaving every amount uncapped if none were set
+pub fn get_caps(e: &Env, token: &BytesN<32>) -> ReserveCaps {
+    if let Some(caps) = e.data().get::<DataKey, ReserveCaps>(DataKey::Caps(token.clone())) {
+        caps.unwrap()
+    } else {
+        ReserveCaps { supply_cap: 0, borrow_cap: 0, user_borrow_cap: 0 }
+    }
+}
+
+pub fn set_caps(e: &Env, token: &BytesN<32>, caps: &ReserveCaps) {
+    e.data().set::<DataKey, ReserveCaps>(DataKey::Caps(token.clone()), caps.clone());
+}
+
+pub fn get_drawn(e: &Env, key: &BytesN<32>) -> i128 {
+    if let Some(drawn) = e.data().get::<DataKey, i128>(DataKey::Drawn(key.clone())) {
+        drawn.unwrap()
+    } else {
+        0
+    }
+}
+
+pub fn set_drawn(e: &Env, key: &BytesN<32>, amount: i128) {
+    e.data().set::<DataKey, i128>(DataKey::Drawn(key.clone()), amount);
+}
+
+pub fn get_session(e: &Env, owner: &Identifier, pubkey: &BytesN<32>) -> Option<Session> {
+    let key = DataKey::SessionKey(SessionDataKey { owner: owner.clone(), pubkey: pubkey.clone() });
+    e.data().get::<DataKey, Session>(key).map(|session| session.unwrap())
+}
+
+pub fn set_session(e: &Env, owner: &Identifier, pubkey: &BytesN<32>, session: &Session) {
+    let key = DataKey::SessionKey(SessionDataKey { owner: owner.clone(), pubkey: pubkey.clone() });
+    e.data().set::<DataKey, Session>(key, session.clone());
+}
+
+pub fn remove_session(e: &Env, owner: &Identifier, pubkey: &BytesN<32>) {
+    let key = DataKey::SessionKey(SessionDataKey { owner: owner.clone(), pubkey: pubkey.clone() });
+    e.data().remove(key);
+}
+
+pub fn get_config(e: &Env) -> PoolConfig {
+    e.data().get::<DataKey, PoolConfig>(DataKey::Config).unwrap().unwrap()
+}
+
+pub fn set_config(e: &Env, config: &PoolConfig) {
+    e.data().set::<DataKey, PoolConfig>(DataKey::Config, config.clone());
+}
+
+pub fn get_oracle(e: &Env) -> Option<OracleConfig> {
+    e.data().get::<DataKey, OracleConfig>(DataKey::Oracle).map(|config| config.unwrap())
+}
+
+pub fn set_oracle(e: &Env, config: &OracleConfig) {
+    e.data().set::<DataKey, OracleConfig>(DataKey::Oracle, config.clone());
+}
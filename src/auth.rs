@@ -1,24 +1,21 @@
-use crate::pool::DataKey;
+use crate::{errors::PoolError, events, pool::{DataKey, NonceWordKey}};
 use soroban_auth::{Identifier, Signature};
-use soroban_sdk::{Env, BigInt, panic_error, contracterror};
-
-#[contracterror]
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub enum Error {
-    IncorrectNonceForInvoker = 1,
-    IncorrectNonce = 2,
-}
+use soroban_sdk::{Env, panic_error};
 
 pub fn verify_and_consume_nonce(env: &Env, sig: &Signature, nonce: &i64) {
     match sig {
         Signature::Invoker => {
-            if BigInt::zero(env) != nonce {
-                panic_error!(env, Error::IncorrectNonceForInvoker);
+            if *nonce != 0 {
+                panic_error!(env, PoolError::IncorrectNonceForInvoker);
             }
         }
         Signature::Ed25519(_) | Signature::Account(_) => {
             let id = sig.identifier(env);
+            if *nonce != get_nonce(env, &id) {
+                panic_error!(env, PoolError::IncorrectNonce);
+            }
             set_nonce(env, &id, nonce + 1);
+            events::nonce_used(env, id, *nonce);
         }
     }
 }
@@ -31,7 +28,96 @@ pub fn get_nonce(env: &Env, id: &Identifier) -> i64 {
         .unwrap()
 }
 
+/// The ordered nonce a signature is expected to carry. The invoker authorizes without a
+/// nonce, even when it has ordered nonces of its own from signing as an account.
+pub fn auth_nonce(env: &Env, sig: &Signature, id: &Identifier) -> i64 {
+    match sig {
+        Signature::Invoker => 0,
+        Signature::Ed25519(_) | Signature::Account(_) => get_nonce(env, id),
+    }
+}
+
 pub fn set_nonce(env: &Env, id: &Identifier, nonce: i64) {
     let key = DataKey::Nonce(id.clone());
     env.data().set(key, nonce);
-}
\ No newline at end of file
+}
+
+/// Move the nonce of the identifier forward to `nonce`, invalidating every signature
+/// over an earlier nonce. Nonces never move backwards.
+pub fn advance_nonce(env: &Env, id: &Identifier, nonce: i64) {
+    if nonce > get_nonce(env, id) {
+        set_nonce(env, id, nonce);
+    }
+}
+
+// ***** Unordered Nonces *****
+//
+// Nonces are tracked as a bitmap of 64 nonces per word, so any unused nonce
+// is valid and signatures can be consumed in any order.
+
+/// Verify the nonce has not been used by the signer and mark it as used
+pub fn verify_and_consume_unordered_nonce(env: &Env, sig: &Signature, nonce: &i64) {
+    match sig {
+        Signature::Invoker => {
+            if *nonce != 0 {
+                panic_error!(env, PoolError::IncorrectNonceForInvoker);
+            }
+        }
+        Signature::Ed25519(_) | Signature::Account(_) => {
+            let id = sig.identifier(env);
+            if is_nonce_used(env, &id, *nonce) {
+                panic_error!(env, PoolError::NonceAlreadyUsed);
+            }
+            let (word, bit) = nonce_position(env, *nonce);
+            let bitmap = get_nonce_word(env, &id, word);
+            set_nonce_word(env, &id, word, bitmap | (1 << bit));
+            events::nonce_used(env, id, *nonce);
+        }
+    }
+}
+
+/// Check if an unordered nonce has been used by the identifier
+pub fn is_nonce_used(env: &Env, id: &Identifier, nonce: i64) -> bool {
+    let (word, bit) = nonce_position(env, nonce);
+    get_nonce_word(env, id, word) & (1 << bit) != 0
+}
+
+/// Mark every unordered nonce in the range [start, end) as used for the identifier
+pub fn invalidate_nonces(env: &Env, id: &Identifier, start: i64, end: i64) {
+    if start < 0 || end <= start {
+        panic_error!(env, PoolError::InvalidNonceRange);
+    }
+
+    let mut word = start >> 6;
+    while word <= (end - 1) >> 6 {
+        let word_start = word << 6;
+        let lo = if start > word_start { start - word_start } else { 0 };
+        let hi = if end < word_start + 64 { end - word_start } else { 64 };
+        let mask = if hi - lo == 64 { u64::MAX } else { ((1u64 << (hi - lo)) - 1) << lo };
+
+        let bitmap = get_nonce_word(env, id, word);
+        set_nonce_word(env, id, word, bitmap | mask);
+        word += 1;
+    }
+}
+
+/// Split a nonce into its word index and bit position within that word
+fn nonce_position(env: &Env, nonce: i64) -> (i64, u32) {
+    if nonce < 0 {
+        panic_error!(env, PoolError::InvalidNonceRange);
+    }
+    (nonce >> 6, (nonce & 63) as u32)
+}
+
+fn get_nonce_word(env: &Env, id: &Identifier, word: i64) -> u64 {
+    let key = DataKey::NonceWord(NonceWordKey { id: id.clone(), word });
+    env.data()
+        .get::<DataKey, u64>(key)
+        .unwrap_or_else(|| Ok(0))
+        .unwrap()
+}
+
+fn set_nonce_word(env: &Env, id: &Identifier, word: i64, bitmap: u64) {
+    let key = DataKey::NonceWord(NonceWordKey { id: id.clone(), word });
+    env.data().set(key, bitmap);
+}
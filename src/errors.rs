@@ -0,0 +1,35 @@
+use soroban_sdk::contracterror;
+
+/// Errors the pool can fail with, surfaced to clients as `ContractError(code)`
+#[contracterror]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PoolError {
+    IncorrectNonceForInvoker = 1,
+    IncorrectNonce = 2,
+    NonceAlreadyUsed = 3,
+    InvalidNonceRange = 4,
+    AlreadyInitialized = 5,
+    NotInitialized = 6,
+    ExpiredSignature = 7,
+    NotEnoughCollateral = 8,
+    InsufficientLiquidity = 9,
+    InvalidAmount = 10,
+    NotEnoughAllowance = 11,
+    InvalidRateModel = 12,
+    InvalidConfig = 13,
+    NotLiquidatable = 14,
+    NotAuthorized = 15,
+    ReserveNotFound = 16,
+    ReserveAlreadyExists = 17,
+    NoOracle = 18,
+    StalePrice = 19,
+    InvalidPrice = 20,
+    FlashLoanActive = 21,
+    FlashLoanNotRepaid = 22,
+    SupplyCapExceeded = 23,
+    BorrowCapExceeded = 24,
+    UserBorrowCapExceeded = 25,
+    Overflow = 26,
+    MaxAmountExceeded = 27,
+    NotYetValid = 28,
+}
@@ -0,0 +1,196 @@
+use crate::pool::ReserveCaps;
+use soroban_auth::Identifier;
+use soroban_sdk::{BytesN, Env, symbol};
+
+// ****** Events *****
+//
+// Topics identify the action, the token of the reserve and the identifiers involved,
+// data holds the amount moved and any resulting balance.
+
+/// Emitted when `owner` deposits tokens with `deposit`
+///
+/// - topics - `["deposit", token: BytesN<32>, owner: Identifier]`
+/// - data - `[amount: i128, collateral: i128]`
+pub fn deposit(e: &Env, token: BytesN<32>, owner: Identifier, amount: i128, collateral: i128) {
+    let topics = (symbol!("deposit"), token, owner);
+    e.events().publish(topics, (amount, collateral));
+}
+
+/// Emitted when `owner` deposits tokens with a token approval signature through `deposit_p`
+///
+/// - topics - `["deposit_p", token: BytesN<32>, owner: Identifier]`
+/// - data - `[amount: i128, collateral: i128]`
+pub fn deposit_permit(e: &Env, token: BytesN<32>, owner: Identifier, amount: i128, collateral: i128) {
+    let topics = (symbol!("deposit_p"), token, owner);
+    e.events().publish(topics, (amount, collateral));
+}
+
+/// Emitted when `receiver` borrows tokens on-behalf-of `owner`
+///
+/// - topics - `["borrow", token: BytesN<32>, owner: Identifier, receiver: Identifier]`
+/// - data - `[amount: i128, liability: i128]`
+pub fn borrow(e: &Env, token: BytesN<32>, owner: Identifier, receiver: Identifier, amount: i128, liability: i128) {
+    let topics = (symbol!("borrow"), token, owner, receiver);
+    e.events().publish(topics, (amount, liability));
+}
+
+/// Emitted when collateral of `owner` is withdrawn to `receiver`
+///
+/// - topics - `["withdraw", token: BytesN<32>, owner: Identifier, receiver: Identifier]`
+/// - data - `[amount: i128, collateral: i128]`
+pub fn withdraw(e: &Env, token: BytesN<32>, owner: Identifier, receiver: Identifier, amount: i128, collateral: i128) {
+    let topics = (symbol!("withdraw"), token, owner, receiver);
+    e.events().publish(topics, (amount, collateral));
+}
+
+/// Emitted when `from` repays the liability of `beneficiary`
+///
+/// - topics - `["repay", token: BytesN<32>, beneficiary: Identifier, from: Identifier]`
+/// - data - `[amount: i128, liability: i128]`
+pub fn repay(e: &Env, token: BytesN<32>, beneficiary: Identifier, from: Identifier, amount: i128, liability: i128) {
+    let topics = (symbol!("repay"), token, beneficiary, from);
+    e.events().publish(topics, (amount, liability));
+}
+
+/// Emitted when `liquidator` repays the liability of `borrower` in exchange for their collateral
+///
+/// - topics - `["liquidate", borrower: Identifier, liquidator: Identifier]`
+/// - data - `[debt_token: BytesN<32>, coll_token: BytesN<32>, repaid: i128, seized: i128]`
+pub fn liquidate(
+    e: &Env,
+    borrower: Identifier,
+    liquidator: Identifier,
+    debt_token: BytesN<32>,
+    coll_token: BytesN<32>,
+    repaid: i128,
+    seized: i128,
+) {
+    let topics = (symbol!("liquidate"), borrower, liquidator);
+    e.events().publish(topics, (debt_token, coll_token, repaid, seized));
+}
+
+/// Emitted when `receiver` takes a flash loan and pays it back with the fee
+///
+/// - topics - `["flash_loan", token: BytesN<32>, receiver: BytesN<32>]`
+/// - data - `[amount: i128, fee: i128]`
+pub fn flash_loan(e: &Env, token: BytesN<32>, receiver: BytesN<32>, amount: i128, fee: i128) {
+    let topics = (symbol!("flash_loan"), token, receiver);
+    e.events().publish(topics, (amount, fee));
+}
+
+/// Emitted when the liability of `borrower` is left without collateral and written off
+///
+/// - topics - `["bad_debt", token: BytesN<32>, borrower: Identifier]`
+/// - data - `amount: i128`
+pub fn bad_debt(e: &Env, token: BytesN<32>, borrower: Identifier, amount: i128) {
+    let topics = (symbol!("bad_debt"), token, borrower);
+    e.events().publish(topics, amount);
+}
+
+/// Emitted when `delegator` sets the borrow allowance of `delegatee`
+///
+/// - topics - `["allowance", token: BytesN<32>, delegator: Identifier, delegatee: Identifier]`
+/// - data - `amount: i128`
+pub fn allowance(e: &Env, token: BytesN<32>, delegator: Identifier, delegatee: Identifier, amount: i128) {
+    let topics = (symbol!("allowance"), token, delegator, delegatee);
+    e.events().publish(topics, amount);
+}
+
+/// Emitted when `owner` registers a session key that can borrow `token` on its behalf
+///
+/// - topics - `["session", owner: Identifier, pubkey: BytesN<32>]`
+/// - data - `[token: BytesN<32>, max_amount: i128, expiration: u64]`
+pub fn session_added(e: &Env, owner: Identifier, pubkey: BytesN<32>, token: BytesN<32>, max_amount: i128, expiration: u64) {
+    let topics = (symbol!("session"), owner, pubkey);
+    e.events().publish(topics, (token, max_amount, expiration));
+}
+
+/// Emitted when `owner` revokes a session key
+///
+/// - topics - `["sess_rm", owner: Identifier, pubkey: BytesN<32>]`
+/// - data - `()`
+pub fn session_removed(e: &Env, owner: Identifier, pubkey: BytesN<32>) {
+    let topics = (symbol!("sess_rm"), owner, pubkey);
+    e.events().publish(topics, ());
+}
+
+/// Emitted when `admin` adds a reserve for `token`
+///
+/// - topics - `["add_res", admin: Identifier]`
+/// - data - `token: BytesN<32>`
+pub fn reserve_added(e: &Env, admin: Identifier, token: BytesN<32>) {
+    let topics = (symbol!("add_res"), admin);
+    e.events().publish(topics, token);
+}
+
+/// Emitted when `admin` sets the maximum loan-to-value of the pool
+///
+/// - topics - `["max_ltv", admin: Identifier]`
+/// - data - `max_ltv: i128`
+pub fn max_ltv(e: &Env, admin: Identifier, max_ltv: i128) {
+    let topics = (symbol!("max_ltv"), admin);
+    e.events().publish(topics, max_ltv);
+}
+
+/// Emitted when `admin` sets the caps of the reserve for `token`
+///
+/// - topics - `["caps", admin: Identifier, token: BytesN<32>]`
+/// - data - `caps: ReserveCaps`
+pub fn caps(e: &Env, admin: Identifier, token: BytesN<32>, caps: ReserveCaps) {
+    let topics = (symbol!("caps"), admin, token);
+    e.events().publish(topics, caps);
+}
+
+/// Emitted when `admin` sets the price oracle of the pool
+///
+/// - topics - `["oracle", admin: Identifier]`
+/// - data - `[oracle: BytesN<32>, max_age: u64]`
+pub fn oracle(e: &Env, admin: Identifier, oracle: BytesN<32>, max_age: u64) {
+    let topics = (symbol!("oracle"), admin);
+    e.events().publish(topics, (oracle, max_age));
+}
+
+/// Emitted when `admin` claims the fees of a reserve to `to`
+///
+/// - topics - `["claim_fees", token: BytesN<32>, admin: Identifier, to: Identifier]`
+/// - data - `amount: i128`
+pub fn fees_claimed(e: &Env, token: BytesN<32>, admin: Identifier, to: Identifier, amount: i128) {
+    let topics = (symbol!("claim_fees"), token, admin, to);
+    e.events().publish(topics, amount);
+}
+
+/// Emitted when a nonce of `id` is consumed
+///
+/// - topics - `["nonce", id: Identifier]`
+/// - data - `nonce: i64`
+pub fn nonce_used(e: &Env, id: Identifier, nonce: i64) {
+    let topics = (symbol!("nonce"), id);
+    e.events().publish(topics, nonce);
+}
+
+/// Emitted when `id` cancels the signatures over every ordered nonce up to and including `nonce`
+///
+/// - topics - `["nonce_can", id: Identifier]`
+/// - data - `[nonce: i64, next: i64]`
+pub fn nonce_cancelled(e: &Env, id: Identifier, nonce: i64, next: i64) {
+    let topics = (symbol!("nonce_can"), id);
+    e.events().publish(topics, (nonce, next));
+}
+
+/// Emitted when `id` skips `n` ordered nonces
+///
+/// - topics - `["nonce_bump", id: Identifier]`
+/// - data - `[n: i64, next: i64]`
+pub fn nonce_bumped(e: &Env, id: Identifier, n: i64, next: i64) {
+    let topics = (symbol!("nonce_bump"), id);
+    e.events().publish(topics, (n, next));
+}
+
+/// Emitted when `id` invalidates the unordered nonces in [start, end)
+///
+/// - topics - `["nonce_inv", id: Identifier]`
+/// - data - `[start: i64, end: i64]`
+pub fn nonces_invalidated(e: &Env, id: Identifier, start: i64, end: i64) {
+    let topics = (symbol!("nonce_inv"), id);
+    e.events().publish(topics, (start, end));
+}
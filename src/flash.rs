@@ -0,0 +1,85 @@
+use soroban_sdk::{contractclient, Bytes, BytesN, Env};
+
+// ****** Flash Loan Receiver Interface *****
+
+/// The interface the pool calls on the receiver of a flash loan
+#[contractclient(name = "FlashReceiverClient")]
+pub trait FlashReceiver {
+    /// Called after `amount` tokens were sent to the receiver. Before returning, the receiver
+    /// must send `amount + fee` tokens back to the pool, which is the invoker of this function.
+    fn on_flash(e: Env, token: BytesN<32>, amount: i128, fee: i128, data: Bytes);
+}
+
+// ****** Mock Flash Loan Receiver *****
+
+#[cfg(any(test, feature = "testutils"))]
+pub use mock::{MockFlashReceiver, MockFlashReceiverClient};
+
+#[cfg(any(test, feature = "testutils"))]
+pub use reentrant::{MockReentrantReceiver, MockReentrantReceiverClient};
+
+#[cfg(any(test, feature = "testutils"))]
+mod mock {
+    use soroban_auth::{Identifier, Signature};
+    use soroban_sdk::{contractimpl, contracttype, BigInt, Bytes, BytesN, Env};
+
+    use crate::math::{from_bigint, to_bigint};
+
+    #[derive(Clone)]
+    #[contracttype]
+    pub enum MockFlashReceiverDataKey {
+        Data,
+    }
+
+    /// A flash loan receiver that pays back the loan and fee out of its own balance,
+    /// sending back everything it holds if that falls short.
+    ///
+    /// For tests only, register it with `e.register_contract`.
+    pub struct MockFlashReceiver;
+
+    #[contractimpl]
+    impl MockFlashReceiver {
+        /// Record the data of the loan and pay it back to the pool
+        pub fn on_flash(e: Env, token: BytesN<32>, amount: i128, fee: i128, data: Bytes) {
+            e.data().set(MockFlashReceiverDataKey::Data, data);
+
+            let pool_id = Identifier::from(e.invoker());
+            let token_client = crate::token::Client::new(&e, token);
+            let balance = from_bigint(&e, &token_client.balance(&Identifier::Contract(e.get_current_contract().into())));
+            let owed = amount + fee;
+            let payment = if balance < owed { balance } else { owed };
+            token_client.xfer(&Signature::Invoker, &BigInt::zero(&e), &pool_id, &to_bigint(&e, payment));
+        }
+
+        /// Get the data passed with the last flash loan
+        pub fn last_data(e: Env) -> Bytes {
+            match e.data().get::<MockFlashReceiverDataKey, Bytes>(MockFlashReceiverDataKey::Data) {
+                Some(data) => data.unwrap(),
+                None => Bytes::new(&e),
+            }
+        }
+    }
+}
+
+#[cfg(any(test, feature = "testutils"))]
+mod reentrant {
+    use soroban_auth::Identifier;
+    use soroban_sdk::{contractimpl, Bytes, BytesN, Env};
+
+    use crate::pool::PoolClient;
+
+    /// A flash loan receiver that calls back into the pool to withdraw while the loan is outstanding.
+    ///
+    /// For tests only, register it with `e.register_contract`.
+    pub struct MockReentrantReceiver;
+
+    #[contractimpl]
+    impl MockReentrantReceiver {
+        /// Call back into the pool while the loan is outstanding
+        pub fn on_flash(e: Env, token: BytesN<32>, amount: i128, _fee: i128, _data: Bytes) {
+            if let Identifier::Contract(pool) = Identifier::from(e.invoker()) {
+                PoolClient::new(&e, pool).withdraw(&token, &amount);
+            }
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use soroban_sdk::{contracttype, Env, panic_error};
+
+use crate::{errors::PoolError, math::{mul_div_ceil, mul_div_floor, SCALAR_7, SCALAR_9}};
+
+pub const SECONDS_PER_YEAR: i128 = 31536000;
+
+/// The highest annual borrow rate a model can reach, 10000% scaled by `SCALAR_7`. Keeps the
+/// growth of the borrow index well within an `i128`.
+pub const MAX_RATE: i128 = 100 * SCALAR_7;
+
+// ****** Rate Models *****
+
+/// Determines the annual borrow rate of a reserve
+pub trait InterestRateModel {
+    /// The annual borrow rate, scaled by `SCALAR_7`, given the utilization of the reserve,
+    /// scaled by `SCALAR_7`
+    fn borrow_rate(&self, utilization: i128) -> i128;
+
+    /// Check the parameters of the model are sane
+    fn is_valid(&self) -> bool;
+}
+
+/// A borrow rate that does not depend on utilization
+#[derive(Clone)]
+#[contracttype]
+pub struct FixedRate {
+    pub rate: i128,
+}
+
+/// A borrow rate that grows linearly with utilization
+#[derive(Clone)]
+#[contracttype]
+pub struct LinearRate {
+    pub base: i128, // the rate at 0% utilization
+    pub slope: i128, // the rate added at 100% utilization
+}
+
+/// A borrow rate that grows slowly with utilization until the kink, and steeply afterwards
+#[derive(Clone)]
+#[contracttype]
+pub struct KinkedRate {
+    pub base: i128, // the rate at 0% utilization
+    pub slope_1: i128, // the rate added between 0% utilization and the kink
+    pub slope_2: i128, // the rate added between the kink and 100% utilization
+    pub kink: i128, // the target utilization
+}
+
+/// The rate model a reserve is added with
+#[derive(Clone)]
+#[contracttype]
+pub enum RateModel {
+    Fixed(FixedRate),
+    Linear(LinearRate),
+    Kinked(KinkedRate),
+}
+
+impl InterestRateModel for FixedRate {
+    fn borrow_rate(&self, _utilization: i128) -> i128 {
+        self.rate
+    }
+
+    fn is_valid(&self) -> bool {
+        self.rate >= 0 && self.rate <= MAX_RATE
+    }
+}
+
+impl InterestRateModel for LinearRate {
+    fn borrow_rate(&self, utilization: i128) -> i128 {
+        self.base + mul_div_floor(self.slope, utilization, SCALAR_7)
+    }
+
+    fn is_valid(&self) -> bool {
+        self.base >= 0 && self.slope >= 0 && self.base <= MAX_RATE && self.slope <= MAX_RATE - self.base
+    }
+}
+
+impl InterestRateModel for KinkedRate {
+    fn borrow_rate(&self, utilization: i128) -> i128 {
+        if utilization <= self.kink {
+            self.base + mul_div_floor(self.slope_1, utilization, self.kink)
+        } else {
+            let excess = utilization - self.kink;
+            self.base + self.slope_1 + mul_div_floor(self.slope_2, excess, SCALAR_7 - self.kink)
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.base >= 0 && self.slope_1 >= 0 && self.slope_2 >= 0 && self.kink > 0 && self.kink < SCALAR_7
+            && self.base <= MAX_RATE && self.slope_1 <= MAX_RATE - self.base
+            && self.slope_2 <= MAX_RATE - self.base - self.slope_1
+    }
+}
+
+impl InterestRateModel for RateModel {
+    fn borrow_rate(&self, utilization: i128) -> i128 {
+        match self {
+            RateModel::Fixed(model) => model.borrow_rate(utilization),
+            RateModel::Linear(model) => model.borrow_rate(utilization),
+            RateModel::Kinked(model) => model.borrow_rate(utilization),
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        match self {
+            RateModel::Fixed(model) => model.is_valid(),
+            RateModel::Linear(model) => model.is_valid(),
+            RateModel::Kinked(model) => model.is_valid(),
+        }
+    }
+}
+
+/// The share of the reserve's tokens that are lent out, scaled by `SCALAR_7`
+pub fn utilization(liabilities: i128, cash: i128) -> i128 {
+    if liabilities <= 0 {
+        return 0;
+    }
+    mul_div_floor(liabilities, SCALAR_7, liabilities + cash)
+}
+
+// ****** Reserve *****
+
+/// Interest bearing state of a reserve
+///
+/// Liabilities are stored as scaled principal (d_tokens) so interest accrues to
+/// every borrower by growing the borrow index. Collateral is stored as shares so the
+/// same interest accrues to every depositor by growing the supply index.
+#[derive(Clone)]
+#[contracttype]
+pub struct ReserveData {
+    pub b_rate: i128, // the borrow index, scaled by `SCALAR_9`
+    pub d_supply: i128, // the total liabilities of the reserve in d_tokens
+    pub s_rate: i128, // the supply index (tokens per share), scaled by `SCALAR_9`
+    pub s_supply: i128, // the total collateral of the reserve in shares
+    pub fees: i128, // accrued interest owed to the treasury and not yet claimed, in tokens
+    pub b_rem: i128, // growth of the borrow index too small to add to it yet, scaled by `SCALAR_7 * SECONDS_PER_YEAR`
+    pub last_time: u64, // the ledger timestamp interest was last accrued at
+}
+
+impl ReserveData {
+    pub fn new(timestamp: u64) -> Self {
+        ReserveData {
+            b_rate: SCALAR_9,
+            d_supply: 0,
+            s_rate: SCALAR_9,
+            s_supply: 0,
+            fees: 0,
+            b_rem: 0,
+            last_time: timestamp,
+        }
+    }
+
+    /// Compound the borrow index at `rate` from the last accrual until `timestamp`, set aside
+    /// the `reserve_factor` share of the accrued interest for the treasury and distribute the
+    /// rest to depositors. Fails with `Overflow` if the borrow index outgrows an `i128`.
+    pub fn accrue(&mut self, e: &Env, rate: i128, reserve_factor: i128, timestamp: u64) {
+        if timestamp <= self.last_time {
+            return;
+        }
+        if self.d_supply > 0 {
+            let elapsed = (timestamp - self.last_time) as i128;
+            // Grow the index in a single product and carry what rounds off to the next accrual,
+            // so frequent accruals charge the same interest as infrequent ones
+            let numerator = match self.b_rate.checked_mul(rate)
+                .and_then(|product| product.checked_mul(elapsed))
+                .and_then(|product| product.checked_add(self.b_rem)) {
+                Some(numerator) => numerator,
+                None => panic_error!(e, PoolError::Overflow),
+            };
+            let growth = numerator / (SCALAR_7 * SECONDS_PER_YEAR);
+            self.b_rem = numerator % (SCALAR_7 * SECONDS_PER_YEAR);
+            let prev_liabilities = mul_div_floor(self.d_supply, self.b_rate, SCALAR_9);
+            self.b_rate = match self.b_rate.checked_add(growth) {
+                Some(b_rate) => b_rate,
+                None => panic_error!(e, PoolError::Overflow),
+            };
+            let interest = mul_div_floor(self.d_supply, self.b_rate, SCALAR_9) - prev_liabilities;
+            let fee = mul_div_floor(interest, reserve_factor, SCALAR_7);
+            self.fees += fee;
+            self.distribute(interest - fee);
+        }
+        self.last_time = timestamp;
+    }
+
+    /// Spread `amount` tokens across every share by growing the supply index, rounding
+    /// in favor of the pool
+    pub fn distribute(&mut self, amount: i128) {
+        if self.s_supply > 0 {
+            self.s_rate += mul_div_floor(amount, SCALAR_9, self.s_supply);
+        }
+    }
+
+    /// Spread a loss of `amount` tokens across every share by shrinking the supply index,
+    /// rounding in favor of the pool
+    pub fn socialize_loss(&mut self, amount: i128) {
+        if self.s_supply > 0 {
+            self.s_rate -= mul_div_ceil(amount, SCALAR_9, self.s_supply);
+        }
+    }
+
+    /// The tokens owed for an amount of d_tokens, rounding in favor of the pool
+    pub fn to_asset_from_d_token(&self, d_tokens: i128) -> i128 {
+        mul_div_ceil(d_tokens, self.b_rate, SCALAR_9)
+    }
+
+    /// The d_tokens to mint when borrowing an amount of tokens, rounding in favor of the pool
+    pub fn to_d_token_up(&self, amount: i128) -> i128 {
+        mul_div_ceil(amount, SCALAR_9, self.b_rate)
+    }
+
+    /// The d_tokens to burn when repaying an amount of tokens, rounding in favor of the pool
+    pub fn to_d_token_down(&self, amount: i128) -> i128 {
+        mul_div_floor(amount, SCALAR_9, self.b_rate)
+    }
+
+    /// The tokens an amount of shares can be redeemed for, rounding in favor of the pool
+    pub fn to_assets(&self, shares: i128) -> i128 {
+        mul_div_floor(shares, self.s_rate, SCALAR_9)
+    }
+
+    /// The shares to mint when depositing an amount of tokens, rounding in favor of the pool
+    pub fn to_shares(&self, amount: i128) -> i128 {
+        mul_div_floor(amount, SCALAR_9, self.s_rate)
+    }
+
+    /// The shares to burn when withdrawing an amount of tokens, rounding in favor of the pool
+    pub fn to_shares_up(&self, amount: i128) -> i128 {
+        mul_div_ceil(amount, SCALAR_9, self.s_rate)
+    }
+}
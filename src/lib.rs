@@ -5,7 +5,13 @@ extern crate std;
 
 mod accounting;
 mod auth;
+mod events;
+mod math;
 
+pub mod errors;
+pub mod flash;
+pub mod interest;
+pub mod oracle;
 pub mod pool;
 pub mod token {
     soroban_sdk::contractimport!(file = "./soroban_token_spec.wasm");
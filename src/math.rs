@@ -0,0 +1,51 @@
+use soroban_sdk::{BigInt, Env, panic_error};
+
+use crate::errors::PoolError;
+
+// ****** Fixed Point Math *****
+
+/// Fixed point scalar for rates and ratios (7 decimals)
+pub const SCALAR_7: i128 = 1_0000000;
+
+/// Fixed point scalar for indexes (9 decimals)
+pub const SCALAR_9: i128 = 1_000_000_000;
+
+/// The largest amount of tokens, shares or d_tokens the pool accounts for. Leaves enough
+/// headroom that multiplying any amount by an index or price scaled by up to 1e18 fits in an `i128`.
+pub const MAX_AMOUNT: i128 = i128::MAX / (SCALAR_9 * SCALAR_9);
+
+/// Compute `x * y / denominator` rounding down
+pub fn mul_div_floor(x: i128, y: i128, denominator: i128) -> i128 {
+    (x * y) / denominator
+}
+
+/// Compute `x * y / denominator` rounding up
+pub fn mul_div_ceil(x: i128, y: i128, denominator: i128) -> i128 {
+    (x * y + denominator - 1) / denominator
+}
+
+/// Compute `x + y`, failing if the result exceeds `MAX_AMOUNT`
+pub fn checked_add(e: &Env, x: i128, y: i128) -> i128 {
+    match x.checked_add(y) {
+        Some(sum) if sum <= MAX_AMOUNT => sum,
+        _ => panic_error!(e, PoolError::Overflow),
+    }
+}
+
+// ****** BigInt Conversions *****
+
+/// Convert an amount to the `BigInt` the token contract expects
+pub fn to_bigint(e: &Env, amount: i128) -> BigInt {
+    let high = BigInt::from_i64(e, (amount >> 64) as i64);
+    (high << 64) + BigInt::from_u64(e, amount as u64)
+}
+
+/// Convert a token balance to an amount, failing if it is negative or exceeds `MAX_AMOUNT`
+pub fn from_bigint(e: &Env, amount: &BigInt) -> i128 {
+    if *amount < BigInt::zero(e) || *amount > to_bigint(e, MAX_AMOUNT) {
+        panic_error!(e, PoolError::Overflow);
+    }
+    let high = (amount.clone() >> 64).to_u64() as i128;
+    let low = (amount.clone() & BigInt::from_u64(e, u64::MAX)).to_u64() as i128;
+    (high << 64) | low
+}
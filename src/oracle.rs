@@ -0,0 +1,57 @@
+use soroban_sdk::{contractclient, contracttype, BytesN, Env};
+
+// ****** Oracle Interface *****
+
+/// A price reported by an oracle
+#[derive(Clone)]
+#[contracttype]
+pub struct PriceData {
+    pub price: i128, // the value of one unit of the asset in the pool's base unit, scaled by 1e7
+    pub timestamp: u64, // the ledger timestamp the price was last updated at
+}
+
+/// The interface the pool expects from a price oracle contract
+#[contractclient(name = "OracleClient")]
+pub trait OracleTrait {
+    /// Get the latest price of an asset
+    fn price(e: Env, asset: BytesN<32>) -> PriceData;
+}
+
+// ****** Mock Oracle *****
+
+#[cfg(any(test, feature = "testutils"))]
+pub use mock::{MockOracle, MockOracleClient};
+
+#[cfg(any(test, feature = "testutils"))]
+mod mock {
+    use soroban_sdk::{contractimpl, contracttype, BytesN, Env};
+
+    use super::PriceData;
+
+    #[derive(Clone)]
+    #[contracttype]
+    pub enum MockOracleDataKey {
+        Price(BytesN<32>),
+    }
+
+    /// An oracle that reports whatever prices it is given. Anyone can set a price.
+    ///
+    /// For tests only, register it with `e.register_contract`.
+    pub struct MockOracle;
+
+    #[contractimpl]
+    impl MockOracle {
+        /// Set the price of an asset and the timestamp it was observed at
+        pub fn set_price(e: Env, asset: BytesN<32>, price: i128, timestamp: u64) {
+            e.data().set(MockOracleDataKey::Price(asset), PriceData { price, timestamp });
+        }
+
+        /// Get the latest price of an asset, or a zero price if none was set
+        pub fn price(e: Env, asset: BytesN<32>) -> PriceData {
+            match e.data().get::<MockOracleDataKey, PriceData>(MockOracleDataKey::Price(asset)) {
+                Some(data) => data.unwrap(),
+                None => PriceData { price: 0, timestamp: 0 },
+            }
+        }
+    }
+}
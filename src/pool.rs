@@ -1,99 +1,531 @@
 use soroban_auth::{Identifier, Signature, verify};
-use soroban_sdk::{contractimpl, contracttype, BigInt, Env, BytesN, symbol};
+use soroban_sdk::{contractimpl, contracttype, serde::Serialize, BigInt, Bytes, Env, BytesN, Map, Vec, panic_error, symbol};
 
-use crate::{accounting::{get_collateral, set_collateral, get_liabilities, set_liabilities}, auth::{get_nonce, verify_and_consume_nonce}};
+use crate::{
+    errors::PoolError,
+    events,
+    flash::FlashReceiverClient,
+    interest::{InterestRateModel, RateModel, ReserveData, utilization},
+    math::{checked_add, from_bigint, mul_div_ceil, mul_div_floor, to_bigint, MAX_AMOUNT, SCALAR_7, SCALAR_9},
+    oracle::OracleClient,
+    accounting::{
+        get_collateral, set_collateral, get_liabilities, set_liabilities, get_borrow_allowance, set_borrow_allowance,
+        get_delegated_liabilities, set_delegated_liabilities, get_delegatees, get_reserve_data, set_reserve_data,
+        get_config, set_config, get_admin, set_admin, get_reserves, set_reserves, get_reserve_config, set_reserve_config,
+        get_oracle, set_oracle, get_caps, set_caps, get_drawn, set_drawn, get_session, set_session,
+        remove_session
+    },
+    auth::{get_nonce, auth_nonce, advance_nonce, verify_and_consume_nonce, verify_and_consume_unordered_nonce, is_nonce_used, invalidate_nonces}
+};
 
 // ****** Contract Storage *****
 
+/// Parameters of the pool set at initialization
+#[derive(Clone)]
+#[contracttype]
+pub struct PoolConfig {
+    pub close_factor: i128, // the share of a liability that can be repaid in a single liquidation, scaled by 1e7
+    pub liq_bonus: i128, // the extra collateral a liquidator seizes for the amount repaid, scaled by 1e7
+    pub max_ltv: i128, // the share of collateral that can be borrowed against before liquidation, scaled by 1e7
+    pub flash_fee: i128, // the share of a flash loan charged as a fee to depositors of the reserve, scaled by 1e7
+    pub reserve_factor: i128, // the share of accrued interest set aside for the treasury, scaled by 1e7
+}
+
+/// Parameters of a reserve set when the reserve is added
+#[derive(Clone)]
+#[contracttype]
+pub struct ReserveConfig {
+    pub rate_model: RateModel, // the interest rate model for liabilities of the reserve
+}
+
+/// Limits on the size of a reserve, in tokens. A cap of 0 leaves the amount uncapped.
+#[derive(Clone)]
+#[contracttype]
+pub struct ReserveCaps {
+    pub supply_cap: i128, // the most collateral the reserve can hold across every depositor
+    pub borrow_cap: i128, // the most liabilities the reserve can have across every borrower
+    pub user_borrow_cap: i128, // the most liabilities a single identifier can have in the reserve
+}
+
+/// The price oracle used to value positions that span more than one reserve
+#[derive(Clone)]
+#[contracttype]
+pub struct OracleConfig {
+    pub oracle: BytesN<32>, // address of a contract implementing `OracleTrait`
+    pub max_age: u64, // the oldest a price can be, in seconds, before it is considered stale
+}
+
+/// Who receives the tokens borrowed against a `borrow_to` signature
+#[derive(Clone)]
+#[contracttype]
+pub enum BorrowTo {
+    Bearer, // whoever submits the signature
+    Named(Identifier), // the identifier chosen by the signer, regardless of who submits the signature
+}
+
+/// When a signature can be used, as an inclusive window of ledger timestamps or sequence numbers
+#[derive(Clone)]
+#[contracttype]
+pub enum Validity {
+    Time(ValidityWindow), // bounds on the ledger timestamp, in seconds
+    Sequence(ValidityWindow), // bounds on the ledger sequence number
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct ValidityWindow {
+    pub valid_after: u64, // the earliest the signature can be used
+    pub expiration: u64, // the latest the signature can be used
+}
+
+/// What a session key can borrow on-behalf-of the owner that registered it
+#[derive(Clone)]
+#[contracttype]
+pub struct SessionScope {
+    pub token: BytesN<32>, // the token of the reserve the session key can borrow from
+    pub max_amount: i128, // the most tokens the session key can borrow in total
+    pub expiration: u64, // the latest the session key can be used
+    pub receivers: Vec<Identifier>, // the senders the session key can sign `borrow_ses` messages for
+}
+
+/// A registered session key and the tokens borrowed with it so far
+#[derive(Clone)]
+#[contracttype]
+pub struct Session {
+    pub scope: SessionScope,
+    pub drawn: i128,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct UserReserveKey {
+    pub token: BytesN<32>,
+    pub id: Identifier,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct DelegationDataKey {
+    pub token: BytesN<32>,
+    pub delegator: Identifier,
+    pub delegatee: Identifier,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct NonceWordKey {
+    pub id: Identifier,
+    pub word: i64,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct SessionDataKey {
+    pub owner: Identifier,
+    pub pubkey: BytesN<32>,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
-    Token, // address of the token the pool operates with
+    Admin, // the identifier allowed to update the pool parameters
+    Config, // the loan-to-value and liquidation parameters of the pool
+    Reserves, // addresses of the tokens the pool operates with
+    Oracle, // the price oracle and staleness limit of the pool
+    FlashLock, // set while a flash loan is outstanding
+    ResConfig(BytesN<32>), // the interest rate model of a reserve
+    Reserve(BytesN<32>), // the borrow index and total liabilities of a reserve
+    Caps(BytesN<32>), // the supply and borrow caps of a reserve
     Nonce(Identifier), // nonces for auth'ing `borrow_obo`
-    Liability(Identifier), // any tokens owed to the pool
-    Collateral(Identifier) // credit for tokens from the pool
+    Liability(UserReserveKey), // any tokens of a reserve owed to the pool, in d_tokens
+    Collateral(UserReserveKey), // credit for tokens of a reserve from the pool, in shares
+    BorrowAllowance(DelegationDataKey), // standing permission for a delegatee to borrow against a delegator's collateral
+    NonceWord(NonceWordKey), // bitmap of used unordered nonces for auth'ing `borrow_un`
+    DelegatedLiability(DelegationDataKey), // d_tokens a delegatee borrowed on-behalf-of a delegator and still owes
+    Delegatees(UserReserveKey), // delegatees with outstanding delegated liabilities of a reserve for a delegator
+    Drawn(BytesN<32>), // tokens borrowed against a `borrow_max` signature, keyed by the hash of its payload
+    SessionKey(SessionDataKey) // the scope of a session key registered by an owner
 }
 
 // ****** Contract *****
 
 /// A pool of tokens.
-/// 
-/// Allows users to deposit the token and borrow on-behalf-of the depositor
-/// from a separate address.
-/// 
+///
+/// Allows users to deposit any of the pool's tokens and borrow any of them on-behalf-of
+/// the depositor from a separate address. Collateral and liabilities in different tokens
+/// are valued with prices from an oracle. Liabilities accrue interest based on the rate model of their
+/// reserve, which is shared among depositors of that reserve.
+///
 /// This is a completely contrived and unsafe example to showcase interesting auth mechanics in Soroban.
 pub struct Pool;
 
+/// Contract function names are limited to 10 characters, so longer names are abbreviated
 pub trait PoolTrait {
-    /// Initialize the pool with a token
-    fn initialize(e: Env, token: BytesN<32>);
+    /// Initialize the pool with an admin, the maximum loan-to-value, the liquidation parameters
+    /// and the fees of the pool
+    fn initialize(e: Env, admin: Identifier, config: PoolConfig);
+
+    /// The admin adds a reserve for a token with the interest rate model for its liabilities
+    ///
+    /// Signature(admin: Identifier, nonce: i64, token: BytesN<32>, config: ReserveConfig)
+    fn add_res(e: Env, sig: Signature, token: BytesN<32>, config: ReserveConfig);
+
+    /// The admin sets the share of collateral that can be borrowed against, scaled by 1e7.
+    /// Borrowers whose liabilities exceed it can be liquidated.
+    ///
+    /// Signature(admin: Identifier, nonce: i64, max_ltv: i128)
+    fn set_ltv(e: Env, sig: Signature, max_ltv: i128);
+
+    /// The admin sets the supply and borrow caps of a reserve
+    ///
+    /// Signature(admin: Identifier, nonce: i64, token: BytesN<32>, caps: ReserveCaps)
+    fn set_caps(e: Env, sig: Signature, token: BytesN<32>, caps: ReserveCaps);
+
+    /// The admin sets the price oracle used to value positions in more than one reserve,
+    /// and the age in seconds after which its prices are considered stale
+    ///
+    /// Signature(admin: Identifier, nonce: i64, oracle: BytesN<32>, max_age: u64)
+    fn set_oracle(e: Env, sig: Signature, oracle: BytesN<32>, max_age: u64);
 
     /// The sender deposits tokens into the pool and is accredited
-    /// the deposit amount as collateral.
-    fn deposit(e: Env, amount: i64);
+    /// shares of the token's reserve for the deposit amount as collateral.
+    fn deposit(e: Env, token: BytesN<32>, amount: i128);
 
     /// The sender deposits tokens into the pool and is accredited
-    /// the deposit amount as collateral.
-    /// 
+    /// shares of the token's reserve for the deposit amount as collateral.
+    ///
     /// Showcase permit-style auth technique with native token contract
-    fn deposit_p(e: Env, token_approval_sig: Signature, amount: i64);
+    fn deposit_p(e: Env, token_approval_sig: Signature, token: BytesN<32>, amount: i128);
 
     /// The sender borrows tokens on-behalf-of another address that provides
-    /// permission via a signed message that is valid until expiration
-    /// 
-    /// Signature(owner: Identifier, nonce: i64, receiver: Identifier, amount: i64, expiration: u64)
-    /// 
+    /// permission via a signed message that is valid until expiration. The signer's
+    /// collateral in every reserve backs the liability, valued with oracle prices when
+    /// it spans more than one reserve.
+    ///
+    /// Signature(owner: Identifier, nonce: i64, receiver: Identifier, token: BytesN<32>, amount: i128, expiration: u64)
+    ///
     /// Showcase custom auth usage to implement "on-behalf-of"
-    fn borrow_obo(e: Env, sig: Signature, amount: i64, expiration: u64);
+    fn borrow_obo(e: Env, sig: Signature, token: BytesN<32>, amount: i128, expiration: u64);
+
+    /// The signer registers an ed25519 session key that can sign `borrow_ses` messages on-behalf-of
+    /// the signer within the scope. The session key co-signs the registration to prove the signer
+    /// controls it. Replaces the scope of the session key if it is already registered by the signer,
+    /// and resets the tokens borrowed with it.
+    ///
+    /// Signature(owner: Identifier, nonce: i64, pubkey: BytesN<32>, scope: SessionScope)
+    ///
+    /// Session signature(session_key: Identifier, nonce: i64, owner: Identifier, scope: SessionScope)
+    fn add_sess(e: Env, sig: Signature, session_sig: Signature, scope: SessionScope);
+
+    /// The sender borrows tokens on-behalf-of the owner via a message signed by a session key the owner
+    /// registered with `add_sess`, valid until expiration. The borrow must fall within the scope the
+    /// session key was registered with.
+    ///
+    /// Signature(session_key: Identifier, nonce: i64, owner: Identifier, receiver: Identifier, token: BytesN<32>, amount: i128, expiration: u64)
+    fn borrow_ses(e: Env, sig: Signature, owner: Identifier, token: BytesN<32>, amount: i128, expiration: u64);
+
+    /// The signer revokes a session key it registered
+    ///
+    /// Signature(owner: Identifier, nonce: i64, pubkey: BytesN<32>)
+    fn rm_sess(e: Env, sig: Signature, pubkey: BytesN<32>);
+
+    /// The sender redeems a signed message on-behalf-of the signer that is valid until expiration.
+    /// Unlike `borrow_obo`, the signature does not name the sender. The tokens go to the sender for
+    /// `BorrowTo::Bearer`, or to the named identifier for `BorrowTo::Named` whoever the sender is.
+    ///
+    /// Signature(owner: Identifier, nonce: i64, to: BorrowTo, token: BytesN<32>, amount: i128, expiration: u64)
+    fn borrow_to(e: Env, sig: Signature, to: BorrowTo, token: BytesN<32>, amount: i128, expiration: u64);
+
+    /// The sender borrows tokens on-behalf-of another address that provides permission via a signed
+    /// message. Unlike `borrow_obo`, the signature is only valid within a window of ledger timestamps
+    /// or sequence numbers, so it can be post-dated. Like `borrow_un`, the signer picks any unused nonce,
+    /// so a post-dated signature doesn't hold up the signer's other signatures.
+    ///
+    /// Signature(owner: Identifier, nonce: i64, receiver: Identifier, token: BytesN<32>, amount: i128, validity: Validity)
+    fn borrow_win(e: Env, sig: Signature, nonce: i64, token: BytesN<32>, amount: i128, validity: Validity);
+
+    /// The signer grants the delegatee a standing allowance to borrow tokens on-behalf-of
+    /// the signer. Replaces any existing allowance for the delegatee and token.
+    ///
+    /// Signature(owner: Identifier, nonce: i64, delegatee: Identifier, token: BytesN<32>, amount: i128)
+    ///
+    /// Showcase credit delegation with either the invoker or a signed message
+    fn approve_b(e: Env, sig: Signature, delegatee: Identifier, token: BytesN<32>, amount: i128);
+
+    /// The sender borrows tokens on-behalf-of another address that provides
+    /// permission via a signed message that is valid until expiration. Unlike `borrow_obo`,
+    /// the signer picks any unused nonce, so several signatures can be outstanding at once.
+    ///
+    /// Signature(owner: Identifier, nonce: i64, receiver: Identifier, token: BytesN<32>, amount: i128, expiration: u64)
+    fn borrow_un(e: Env, sig: Signature, nonce: i64, token: BytesN<32>, amount: i128, expiration: u64);
+
+    /// The sender borrows tokens on-behalf-of another address that provides permission via a
+    /// signed message for up to `max_amount` tokens, valid until expiration. The signature can be
+    /// used repeatedly until the tokens borrowed against it reach `max_amount`. The signer picks an
+    /// unordered nonce, and can cancel the signature by invalidating it with `inval_nonc`.
+    ///
+    /// Signature(owner: Identifier, nonce: i64, receiver: Identifier, token: BytesN<32>, max_amount: i128, expiration: u64)
+    fn borrow_max(e: Env, sig: Signature, nonce: i64, token: BytesN<32>, max_amount: i128, amount: i128, expiration: u64);
+
+    /// The signer marks every unordered nonce in the range [start, end) as used,
+    /// invalidating any outstanding `borrow_un` signatures within it
+    ///
+    /// Signature(owner: Identifier, nonce: i64, start: i64, end: i64)
+    fn inval_nonc(e: Env, sig: Signature, start: i64, end: i64);
+
+    /// The signer cancels any outstanding signatures over ordered nonces up to and including `nonce`,
+    /// such as a `borrow_obo` signature handed to a delegatee that has not been used yet
+    ///
+    /// Signature(owner: Identifier, nonce: i64, cancel: i64)
+    fn cncl_nonce(e: Env, sig: Signature, cancel: i64);
+
+    /// The signer skips the next `n` ordered nonces, cancelling any outstanding signatures over them
+    ///
+    /// Signature(owner: Identifier, nonce: i64, n: i64)
+    fn bump_nonce(e: Env, sig: Signature, n: i64);
+
+    /// The signer revokes any standing borrow allowance of the token granted to the delegatee
+    ///
+    /// Signature(owner: Identifier, nonce: i64, delegatee: Identifier, token: BytesN<32>)
+    fn revoke_b(e: Env, sig: Signature, delegatee: Identifier, token: BytesN<32>);
+
+    /// The sender borrows tokens on-behalf-of the delegator, drawing down the
+    /// standing allowance of the token the delegator granted to the sender
+    fn borrow_a(e: Env, delegator: Identifier, token: BytesN<32>, amount: i128);
+
+    /// The sender withdraws tokens from their collateral balance. The remaining
+    /// collateral must still cover the sender's liabilities at the maximum loan-to-value.
+    fn withdraw(e: Env, token: BytesN<32>, amount: i128);
+
+    /// The sender withdraws collateral on-behalf-of another address to a receiver
+    /// chosen by that address via a signed message that is valid until expiration.
+    /// The remaining collateral must still cover the signer's liabilities at the maximum loan-to-value.
+    ///
+    /// Signature(owner: Identifier, nonce: i64, receiver: Identifier, token: BytesN<32>, amount: i128, expiration: u64)
+    fn withdr_obo(e: Env, sig: Signature, receiver: Identifier, token: BytesN<32>, amount: i128, expiration: u64);
+
+    /// The sender repays their own liability. Only the outstanding liability
+    /// is pulled from the sender, any surplus is left with the sender.
+    fn repay(e: Env, token: BytesN<32>, amount: i128);
+
+    /// The sender repays the liability of the beneficiary. Only the outstanding liability
+    /// is pulled from the sender, any surplus is left with the sender.
+    fn repay_for(e: Env, beneficiary: Identifier, token: BytesN<32>, amount: i128);
+
+    /// The signer of the approval repays the liability of the beneficiary.
+    /// Only the outstanding liability is pulled from the signer.
+    ///
+    /// Showcase permit-style auth technique with native token contract
+    fn repay_p(e: Env, token_approval_sig: Signature, beneficiary: Identifier, token: BytesN<32>, amount: i128);
+
+    /// The sender repays part of the `debt_token` liability of a borrower whose collateral at the
+    /// maximum loan-to-value no longer covers their liabilities, and is accredited the borrower's `coll_token` collateral for
+    /// the amount repaid plus the liquidation bonus. At most the close factor of the liability
+    /// can be repaid at once.
+    ///
+    /// Requires approval for `transfer_from` before running
+    fn liquidate(e: Env, borrower: Identifier, debt_token: BytesN<32>, coll_token: BytesN<32>, repay_amount: i128);
+
+    /// Lend tokens of a reserve to the receiver contract for the duration of a call to its
+    /// `on_flash` function, passing along `data`. The pool's balance of the token must come back
+    /// with the flash loan fee by the time the call returns. The fee goes to the depositors of the reserve.
+    ///
+    /// Deposits, withdrawals, borrows and repayments are not accepted while the loan is outstanding.
+    fn flash_loan(e: Env, receiver: BytesN<32>, token: BytesN<32>, amount: i128, data: Bytes);
+
+    /// The admin sends the interest of a reserve set aside for the treasury to `to`
+    ///
+    /// Signature(admin: Identifier, nonce: i64, token: BytesN<32>, to: Identifier)
+    fn claim_fees(e: Env, sig: Signature, token: BytesN<32>, to: Identifier);
 
     // ***** View *****
 
-    /// Get the token for the pool
-    fn get_token(e: Env) -> BytesN<32>;
+    /// Get the tokens the pool has reserves for
+    fn reserves(e: Env) -> Vec<BytesN<32>>;
+
+    /// Get the current collateral a user has in a reserve, including earned interest
+    fn collateral(e: Env, token: BytesN<32>, id: Identifier) -> i128;
+
+    /// Get the shares of a reserve a user has
+    fn shares(e: Env, token: BytesN<32>, id: Identifier) -> i128;
+
+    /// Get the total shares of a reserve
+    fn tot_shares(e: Env, token: BytesN<32>) -> i128;
 
-    /// Get the current collateral a user has
-    fn collateral(e: Env, id: Identifier) -> i64;
+    /// Get the current collateral of every depositor in a reserve, including earned interest
+    fn tot_coll(e: Env, token: BytesN<32>) -> i128;
 
-    /// Get the current liability a user has
-    fn liability(e: Env, id: Identifier) -> i64;
+    /// Get the current liabilities of every borrower in a reserve, including accrued interest
+    fn tot_liab(e: Env, token: BytesN<32>) -> i128;
+
+    /// Get the tokens of a reserve held by the pool and available to borrow or withdraw
+    fn avail_liq(e: Env, token: BytesN<32>) -> i128;
+
+    /// Get the share of a reserve's tokens that is lent out, scaled by 1e7
+    fn util_rate(e: Env, token: BytesN<32>) -> i128;
+
+    /// Get the tokens each share of a reserve can be redeemed for, scaled by 1e9
+    fn exch_rate(e: Env, token: BytesN<32>) -> i128;
+
+    /// Get the current liability a user has in a reserve, including accrued interest
+    fn liability(e: Env, token: BytesN<32>, id: Identifier) -> i128;
+
+    /// Get the ratio of a user's collateral in every reserve at the maximum loan-to-value
+    /// to their liabilities, scaled by 1e7. Positions in more than one reserve are valued
+    /// with oracle prices. Below 1e7 the user cannot borrow or withdraw, and can be liquidated.
+    /// `i128::MAX` if the user has no liability.
+    fn health(e: Env, id: Identifier) -> i128;
+
+    /// Get the loan-to-value and liquidation parameters of the pool
+    fn get_config(e: Env) -> PoolConfig;
+
+    /// Get the interest rate model of a reserve
+    fn res_config(e: Env, token: BytesN<32>) -> ReserveConfig;
+
+    /// Get the supply and borrow caps of a reserve
+    fn caps(e: Env, token: BytesN<32>) -> ReserveCaps;
+
+    /// Get the price oracle and staleness limit of the pool
+    fn get_oracle(e: Env) -> OracleConfig;
+
+    /// Get the interest of a reserve set aside for the treasury and not yet claimed
+    fn fees(e: Env, token: BytesN<32>) -> i128;
+
+    /// Get the current annual borrow rate of a reserve, scaled by 1e7
+    fn borrow_rate(e: Env, token: BytesN<32>) -> i128;
 
     /// Fetch the current nonce for the identifier
     fn nonce(e: Env, id: Identifier) -> i64;
+
+    /// Check if an unordered nonce has been used by the identifier
+    fn nonce_used(e: Env, id: Identifier, nonce: i64) -> bool;
+
+    /// Get the tokens that can still be borrowed against a `borrow_max` signature with the given payload.
+    /// 0 once the signature has expired or its nonce has been invalidated.
+    fn remaining(e: Env, owner: Identifier, nonce: i64, receiver: Identifier, token: BytesN<32>, max_amount: i128, expiration: u64) -> i128;
+
+    /// Get the tokens a session key registered by the owner can still borrow.
+    /// 0 once the session key has expired or if it is not registered.
+    fn sess_left(e: Env, owner: Identifier, pubkey: BytesN<32>) -> i128;
+
+    /// Get the liability of a reserve the delegatee borrowed on-behalf-of the delegator and has not repaid
+    fn del_liab(e: Env, delegator: Identifier, delegatee: Identifier, token: BytesN<32>) -> i128;
+
+    /// Get every delegatee with an outstanding liability of a reserve borrowed on-behalf-of the delegator
+    fn delegated(e: Env, delegator: Identifier, token: BytesN<32>) -> Map<Identifier, i128>;
+
+    /// Get the remaining borrow allowance of a token the delegator has granted the delegatee
+    fn allowance(e: Env, delegator: Identifier, delegatee: Identifier, token: BytesN<32>) -> i128;
 }
 
 #[contractimpl]
 impl PoolTrait for Pool {
-    fn initialize(e: Env, token: BytesN<32>) {
-        if e.data().has(DataKey::Token) {
-            panic!("pool already initialized");
+    fn initialize(e: Env, admin: Identifier, config: PoolConfig) {
+        if e.data().has(DataKey::Admin) {
+            panic_error!(&e, PoolError::AlreadyInitialized);
+        }
+        if config.close_factor <= 0 || config.close_factor > SCALAR_7 || config.liq_bonus < 0
+            || config.flash_fee < 0 || config.flash_fee > SCALAR_7
+            || config.reserve_factor < 0 || config.reserve_factor >= SCALAR_7 {
+            panic_error!(&e, PoolError::InvalidConfig);
+        }
+        require_valid_ltv(&e, config.max_ltv);
+
+        set_admin(&e, &admin);
+        set_config(&e, &config);
+        set_reserves(&e, &Vec::new(&e));
+    }
+
+    fn add_res(e: Env, sig: Signature, token: BytesN<32>, config: ReserveConfig) {
+        if !config.rate_model.is_valid() {
+            panic_error!(&e, PoolError::InvalidRateModel);
+        }
+        let signer_id = sig.identifier(&e);
+        let nonce = auth_nonce(&e, &sig, &signer_id);
+
+        verify(&e, &sig, symbol!("add_res"), (&signer_id, &nonce, &token, &config));
+        verify_and_consume_nonce(&e, &sig, &nonce);
+        require_admin(&e, &signer_id);
+        if e.data().has(DataKey::ResConfig(token.clone())) {
+            panic_error!(&e, PoolError::ReserveAlreadyExists);
+        }
+
+        let mut reserves = get_reserves(&e);
+        reserves.push_back(token.clone());
+        set_reserves(&e, &reserves);
+        set_reserve_config(&e, &token, &config);
+        set_reserve_data(&e, &token, &ReserveData::new(e.ledger().timestamp()));
+        events::reserve_added(&e, signer_id, token);
+    }
+
+    fn set_ltv(e: Env, sig: Signature, max_ltv: i128) {
+        require_valid_ltv(&e, max_ltv);
+        let signer_id = sig.identifier(&e);
+        let nonce = auth_nonce(&e, &sig, &signer_id);
+
+        verify(&e, &sig, symbol!("set_ltv"), (&signer_id, &nonce, &max_ltv));
+        verify_and_consume_nonce(&e, &sig, &nonce);
+        require_admin(&e, &signer_id);
+
+        let mut config = get_config(&e);
+        config.max_ltv = max_ltv;
+        set_config(&e, &config);
+        events::max_ltv(&e, signer_id, max_ltv);
+    }
+
+    fn set_caps(e: Env, sig: Signature, token: BytesN<32>, caps: ReserveCaps) {
+        if caps.supply_cap < 0 || caps.borrow_cap < 0 || caps.user_borrow_cap < 0 {
+            panic_error!(&e, PoolError::InvalidConfig);
         }
+        require_reserve(&e, &token);
+        let signer_id = sig.identifier(&e);
+        let nonce = auth_nonce(&e, &sig, &signer_id);
+
+        verify(&e, &sig, symbol!("set_caps"), (&signer_id, &nonce, &token, &caps));
+        verify_and_consume_nonce(&e, &sig, &nonce);
+        require_admin(&e, &signer_id);
+
+        set_caps(&e, &token, &caps);
+        events::caps(&e, signer_id, token, caps);
+    }
 
-        e.data().set(DataKey::Token, token);
+    fn set_oracle(e: Env, sig: Signature, oracle: BytesN<32>, max_age: u64) {
+        let signer_id = sig.identifier(&e);
+        let nonce = auth_nonce(&e, &sig, &signer_id);
+
+        verify(&e, &sig, symbol!("set_oracle"), (&signer_id, &nonce, &oracle, &max_age));
+        verify_and_consume_nonce(&e, &sig, &nonce);
+        require_admin(&e, &signer_id);
+
+        set_oracle(&e, &OracleConfig { oracle: oracle.clone(), max_age });
+        events::oracle(&e, signer_id, oracle, max_age);
     }
 
     /// Requires approval for `transfer_from` before running
-    fn deposit(e: Env, amount: i64) {
+    fn deposit(e: Env, token: BytesN<32>, amount: i128) {
+        require_positive_amount(&e, amount);
         let sender = e.invoker();
         let sender_id = Identifier::from(sender);
-        let token_client = get_token_client(&e);
+        let token_client = get_token_client(&e, &token);
 
         token_client.xfer_from(
             &Signature::Invoker,
             &BigInt::zero(&e),
             &sender_id,
             &get_contract_id(&e),
-            &BigInt::from_i64(&e, amount)
+            &to_bigint(&e, amount)
         );
 
-        let cur_collateral = get_collateral(&e, sender_id.clone());
-        set_collateral(&e, sender_id, cur_collateral + amount);
+        let new_collateral = credit_collateral(&e, &token, sender_id.clone(), amount);
+        events::deposit(&e, token, sender_id, amount, new_collateral);
     }
 
     /// Runs an approval and deposit in the same transaction
-    fn deposit_p(e: Env, token_approval_sig: Signature, amount: i64) {
+    fn deposit_p(e: Env, token_approval_sig: Signature, token: BytesN<32>, amount: i128) {
+        require_positive_amount(&e, amount);
         let sig_id = token_approval_sig.identifier(&e);
-        let token_client = get_token_client(&e);
-        let amount_bi = &BigInt::from_i64(&e, amount);
+        let token_client = get_token_client(&e, &token);
+        let amount_bi = &to_bigint(&e, amount);
 
         // Run the approval
         let sender_nonce = token_client.nonce(&sig_id);
@@ -105,61 +537,528 @@ impl PoolTrait for Pool {
             &BigInt::zero(&e),
             &sig_id,
             &get_contract_id(&e),
-            &BigInt::from_i64(&e, amount)
+            &to_bigint(&e, amount)
         );
 
-        let cur_collateral = get_collateral(&e, sig_id.clone());
-        set_collateral(&e, sig_id, cur_collateral + amount);
+        let new_collateral = credit_collateral(&e, &token, sig_id.clone(), amount);
+        events::deposit_permit(&e, token, sig_id, amount, new_collateral);
     }
 
     /// A signature gives permission to the sender to borrow funds from the signer's collateral balance!
-    fn borrow_obo(e: Env, sig: Signature, amount: i64, expiration: u64) {
-        // Verify signature is not expired
-        if expiration < e.ledger().timestamp() {
-            panic!("expired signature")
-        }
-        
+    fn borrow_obo(e: Env, sig: Signature, token: BytesN<32>, amount: i128, expiration: u64) {
+        require_positive_amount(&e, amount);
+        require_not_expired(&e, expiration);
+
         // Verify that the signature signs and authorizes this invocation.
         let signer_id = sig.identifier(&e);
         let sender_id = Identifier::from(e.invoker());
-        let nonce = get_nonce(&e, &signer_id);
+        let nonce = auth_nonce(&e, &sig, &signer_id);
 
         // By including the `sender` in the signature alongside the `contract_id` and function `symbol`
         // the signer can be ensured nobody other than the sender can execute against this signature
-        verify(&e, &sig, symbol!("borrow_obo"), (&signer_id, &nonce, &sender_id, &amount, &expiration));
+        verify(&e, &sig, symbol!("borrow_obo"), (&signer_id, &nonce, &sender_id, &token, &amount, &expiration));
+        verify_and_consume_nonce(&e, &sig, &nonce);
+
+        do_borrow(&e, &token, signer_id, &sender_id, amount);
+    }
+
+    fn add_sess(e: Env, sig: Signature, session_sig: Signature, scope: SessionScope) {
+        require_positive_amount(&e, scope.max_amount);
+        require_reserve(&e, &scope.token);
+        let signer_id = sig.identifier(&e);
+        let session_id = session_sig.identifier(&e);
+        let pubkey = session_pubkey(&e, &session_id);
+        let nonce = auth_nonce(&e, &sig, &signer_id);
+        let session_nonce = get_nonce(&e, &session_id);
+
+        verify(&e, &sig, symbol!("add_sess"), (&signer_id, &nonce, &pubkey, &scope));
+        verify_and_consume_nonce(&e, &sig, &nonce);
+
+        // The session key names the owner it acts for, so nobody can register a key they don't control
+        verify(&e, &session_sig, symbol!("add_sess"), (&session_id, &session_nonce, &signer_id, &scope));
+        verify_and_consume_nonce(&e, &session_sig, &session_nonce);
+
+        set_session(&e, &signer_id, &pubkey, &Session { scope: scope.clone(), drawn: 0 });
+        events::session_added(&e, signer_id, pubkey, scope.token, scope.max_amount, scope.expiration);
+    }
+
+    fn rm_sess(e: Env, sig: Signature, pubkey: BytesN<32>) {
+        let signer_id = sig.identifier(&e);
+        let nonce = auth_nonce(&e, &sig, &signer_id);
+
+        verify(&e, &sig, symbol!("rm_sess"), (&signer_id, &nonce, &pubkey));
+        verify_and_consume_nonce(&e, &sig, &nonce);
+
+        if get_session(&e, &signer_id, &pubkey).is_some() {
+            remove_session(&e, &signer_id, &pubkey);
+            events::session_removed(&e, signer_id, pubkey);
+        }
+    }
+
+    /// A session key signature gives permission to the sender to borrow funds from the owner's collateral
+    /// balance, within the scope the owner registered the session key with
+    fn borrow_ses(e: Env, sig: Signature, owner: Identifier, token: BytesN<32>, amount: i128, expiration: u64) {
+        require_positive_amount(&e, amount);
+        require_not_expired(&e, expiration);
+
+        // Verify that the session key signs and authorizes this invocation.
+        let session_id = sig.identifier(&e);
+        let pubkey = session_pubkey(&e, &session_id);
+        let sender_id = Identifier::from(e.invoker());
+        let nonce = get_nonce(&e, &session_id);
+
+        verify(&e, &sig, symbol!("borrow_ses"), (&session_id, &nonce, &owner, &sender_id, &token, &amount, &expiration));
+        verify_and_consume_nonce(&e, &sig, &nonce);
+
+        // Check the scope of the session key before the owner's collateral is touched
+        use_session(&e, &owner, &pubkey, &sender_id, &token, amount);
+
+        do_borrow(&e, &token, owner, &sender_id, amount);
+    }
+
+    /// A signature gives permission to anyone holding it to borrow funds from the signer's collateral balance
+    fn borrow_to(e: Env, sig: Signature, to: BorrowTo, token: BytesN<32>, amount: i128, expiration: u64) {
+        require_positive_amount(&e, amount);
+        require_not_expired(&e, expiration);
+
+        // Verify that the signature signs and authorizes this invocation.
+        let signer_id = sig.identifier(&e);
+        let nonce = auth_nonce(&e, &sig, &signer_id);
+
+        // The signer commits to `to` rather than the sender, so a bearer signature can't be
+        // rewritten to name a receiver, nor a named receiver swapped for the relayer
+        verify(&e, &sig, symbol!("borrow_to"), (&signer_id, &nonce, &to, &token, &amount, &expiration));
+        verify_and_consume_nonce(&e, &sig, &nonce);
+
+        let receiver = match to {
+            BorrowTo::Bearer => Identifier::from(e.invoker()),
+            BorrowTo::Named(id) => id,
+        };
+        do_borrow(&e, &token, signer_id, &receiver, amount);
+    }
+
+    /// A signature gives permission to the sender to borrow funds from the signer's collateral balance
+    /// within the window it was signed for
+    fn borrow_win(e: Env, sig: Signature, nonce: i64, token: BytesN<32>, amount: i128, validity: Validity) {
+        require_positive_amount(&e, amount);
+        require_valid(&e, &validity);
+
+        // Verify that the signature signs and authorizes this invocation.
+        let signer_id = sig.identifier(&e);
+        let sender_id = Identifier::from(e.invoker());
+
+        verify(&e, &sig, symbol!("borrow_win"), (&signer_id, &nonce, &sender_id, &token, &amount, &validity));
+        verify_and_consume_unordered_nonce(&e, &sig, &nonce);
+
+        do_borrow(&e, &token, signer_id, &sender_id, amount);
+    }
+
+    /// A signature gives permission to the sender to borrow funds from the signer's collateral balance
+    fn borrow_un(e: Env, sig: Signature, nonce: i64, token: BytesN<32>, amount: i128, expiration: u64) {
+        require_positive_amount(&e, amount);
+        require_not_expired(&e, expiration);
+
+        // Verify that the signature signs and authorizes this invocation.
+        let signer_id = sig.identifier(&e);
+        let sender_id = Identifier::from(e.invoker());
+
+        // The function `symbol` keeps unordered signatures from being replayed against `borrow_obo`
+        verify(&e, &sig, symbol!("borrow_un"), (&signer_id, &nonce, &sender_id, &token, &amount, &expiration));
+        verify_and_consume_unordered_nonce(&e, &sig, &nonce);
+
+        do_borrow(&e, &token, signer_id, &sender_id, amount);
+    }
+
+    /// A signature gives permission to the sender to borrow funds from the signer's collateral balance,
+    /// in as many pieces as needed up to a maximum amount
+    fn borrow_max(e: Env, sig: Signature, nonce: i64, token: BytesN<32>, max_amount: i128, amount: i128, expiration: u64) {
+        require_positive_amount(&e, amount);
+        require_not_expired(&e, expiration);
+
+        // Verify that the signature signs and authorizes this invocation.
+        let signer_id = sig.identifier(&e);
+        let sender_id = Identifier::from(e.invoker());
+
+        // The nonce is not consumed, so the signature stays valid until the maximum amount is drawn
+        verify(&e, &sig, symbol!("borrow_max"), (&signer_id, &nonce, &sender_id, &token, &max_amount, &expiration));
+        if is_nonce_used(&e, &signer_id, nonce) {
+            panic_error!(&e, PoolError::NonceAlreadyUsed);
+        }
+
+        let key = borrow_max_key(&e, &signer_id, nonce, &sender_id, &token, max_amount, expiration);
+        let drawn = checked_add(&e, get_drawn(&e, &key), amount);
+        if drawn > max_amount {
+            panic_error!(&e, PoolError::MaxAmountExceeded);
+        }
+        set_drawn(&e, &key, drawn);
+
+        do_borrow(&e, &token, signer_id, &sender_id, amount);
+    }
+
+    fn inval_nonc(e: Env, sig: Signature, start: i64, end: i64) {
+        let signer_id = sig.identifier(&e);
+        let nonce = auth_nonce(&e, &sig, &signer_id);
+
+        verify(&e, &sig, symbol!("inval_nonc"), (&signer_id, &nonce, &start, &end));
         verify_and_consume_nonce(&e, &sig, &nonce);
 
-        // Check collateral and liability balances
-        let signer_collateral = get_collateral(&e, signer_id.clone());
-        let signer_liability = get_liabilities(&e, signer_id.clone());
-        if signer_collateral < (signer_liability + amount) {
-            panic!("not enough collateral")
+        invalidate_nonces(&e, &signer_id, start, end);
+        events::nonces_invalidated(&e, signer_id, start, end);
+    }
+
+    fn cncl_nonce(e: Env, sig: Signature, cancel: i64) {
+        let signer_id = sig.identifier(&e);
+        let nonce = auth_nonce(&e, &sig, &signer_id);
+
+        verify(&e, &sig, symbol!("cncl_nonce"), (&signer_id, &nonce, &cancel));
+        verify_and_consume_nonce(&e, &sig, &nonce);
+
+        let next = checked_add_nonce(&e, cancel, 1);
+        advance_nonce(&e, &signer_id, next);
+        events::nonce_cancelled(&e, signer_id.clone(), cancel, get_nonce(&e, &signer_id));
+    }
+
+    fn bump_nonce(e: Env, sig: Signature, n: i64) {
+        if n <= 0 {
+            panic_error!(&e, PoolError::InvalidNonceRange);
         }
+        let signer_id = sig.identifier(&e);
+        let nonce = auth_nonce(&e, &sig, &signer_id);
 
-        // Looks good - execute token distribution
-        set_liabilities(&e, signer_id, signer_liability + amount);
+        verify(&e, &sig, symbol!("bump_nonce"), (&signer_id, &nonce, &n));
+        verify_and_consume_nonce(&e, &sig, &nonce);
 
-        let token_client = get_token_client(&e);
-        token_client.xfer(&Signature::Invoker, &BigInt::zero(&e), &sender_id, &BigInt::from_i64(&e, amount));
+        let next = checked_add_nonce(&e, get_nonce(&e, &signer_id), n);
+        advance_nonce(&e, &signer_id, next);
+        events::nonce_bumped(&e, signer_id, n, next);
+    }
+
+    fn approve_b(e: Env, sig: Signature, delegatee: Identifier, token: BytesN<32>, amount: i128) {
+        if amount < 0 {
+            panic_error!(&e, PoolError::InvalidAmount);
+        }
+        require_reserve(&e, &token);
+        let signer_id = sig.identifier(&e);
+        let nonce = auth_nonce(&e, &sig, &signer_id);
+
+        verify(&e, &sig, symbol!("approve_b"), (&signer_id, &nonce, &delegatee, &token, &amount));
+        verify_and_consume_nonce(&e, &sig, &nonce);
+
+        set_borrow_allowance(&e, &token, signer_id.clone(), delegatee.clone(), amount);
+        events::allowance(&e, token, signer_id, delegatee, amount);
+    }
+
+    fn revoke_b(e: Env, sig: Signature, delegatee: Identifier, token: BytesN<32>) {
+        let signer_id = sig.identifier(&e);
+        let nonce = auth_nonce(&e, &sig, &signer_id);
+
+        verify(&e, &sig, symbol!("revoke_b"), (&signer_id, &nonce, &delegatee, &token));
+        verify_and_consume_nonce(&e, &sig, &nonce);
+
+        set_borrow_allowance(&e, &token, signer_id.clone(), delegatee.clone(), 0);
+        events::allowance(&e, token, signer_id, delegatee, 0);
+    }
+
+    fn borrow_a(e: Env, delegator: Identifier, token: BytesN<32>, amount: i128) {
+        require_positive_amount(&e, amount);
+        let sender_id = Identifier::from(e.invoker());
+
+        // Draw down the allowance before touching any balances
+        let allowance = get_borrow_allowance(&e, &token, delegator.clone(), sender_id.clone());
+        if allowance < amount {
+            panic_error!(&e, PoolError::NotEnoughAllowance);
+        }
+        set_borrow_allowance(&e, &token, delegator.clone(), sender_id.clone(), allowance - amount);
+        events::allowance(&e, token.clone(), delegator.clone(), sender_id.clone(), allowance - amount);
+
+        do_borrow(&e, &token, delegator, &sender_id, amount);
+    }
+
+    fn withdraw(e: Env, token: BytesN<32>, amount: i128) {
+        require_positive_amount(&e, amount);
+        let sender_id = Identifier::from(e.invoker());
+        do_withdraw(&e, &token, sender_id.clone(), &sender_id, amount);
+    }
+
+    /// A signature gives permission to the sender to withdraw funds from the signer's collateral balance
+    /// to the receiver named in the signature
+    fn withdr_obo(e: Env, sig: Signature, receiver: Identifier, token: BytesN<32>, amount: i128, expiration: u64) {
+        require_positive_amount(&e, amount);
+        require_not_expired(&e, expiration);
+
+        // Verify that the signature signs and authorizes this invocation.
+        let signer_id = sig.identifier(&e);
+        let nonce = auth_nonce(&e, &sig, &signer_id);
+
+        // The receiver is part of the signature, so the sender can only move funds
+        // to the address the signer chose
+        verify(&e, &sig, symbol!("withdr_obo"), (&signer_id, &nonce, &receiver, &token, &amount, &expiration));
+        verify_and_consume_nonce(&e, &sig, &nonce);
+
+        do_withdraw(&e, &token, signer_id, &receiver, amount);
+    }
+
+    /// Requires approval for `transfer_from` before running
+    fn repay(e: Env, token: BytesN<32>, amount: i128) {
+        let sender_id = Identifier::from(e.invoker());
+        do_repay(&e, &token, &sender_id, sender_id.clone(), amount);
+    }
+
+    /// Requires approval for `transfer_from` before running
+    fn repay_for(e: Env, beneficiary: Identifier, token: BytesN<32>, amount: i128) {
+        let sender_id = Identifier::from(e.invoker());
+        do_repay(&e, &token, &sender_id, beneficiary, amount);
+    }
+
+    /// Runs an approval and repayment in the same transaction
+    fn repay_p(e: Env, token_approval_sig: Signature, beneficiary: Identifier, token: BytesN<32>, amount: i128) {
+        require_positive_amount(&e, amount);
+        let sig_id = token_approval_sig.identifier(&e);
+        let token_client = get_token_client(&e, &token);
+        let amount_bi = to_bigint(&e, amount);
+
+        // Run the approval
+        let sender_nonce = token_client.nonce(&sig_id);
+        token_client.approve(&token_approval_sig, &sender_nonce, &get_contract_id(&e), &amount_bi);
+
+        // Now the pool has the appropriate permissions to run `transfer_from`
+        do_repay(&e, &token, &sig_id, beneficiary, amount);
+    }
+
+    fn liquidate(e: Env, borrower: Identifier, debt_token: BytesN<32>, coll_token: BytesN<32>, repay_amount: i128) {
+        require_positive_amount(&e, repay_amount);
+        require_initialized(&e);
+        let liquidator_id = Identifier::from(e.invoker());
+        let config = get_config(&e);
+
+        // Only borrowers who could no longer borrow or withdraw can be liquidated
+        let (total_collateral, total_liability) = account_totals(&e, &borrower);
+        if health_factor(total_collateral, total_liability, config.max_ltv) >= SCALAR_7 {
+            panic_error!(&e, PoolError::NotLiquidatable);
+        }
+
+        // Limit the repayment by the close factor, and the seized collateral by what the borrower has
+        let debt_reserve = load_reserve(&e, &debt_token);
+        let coll_reserve = load_reserve(&e, &coll_token);
+        let borrower_shares = get_collateral(&e, &coll_token, borrower.clone());
+        let borrower_collateral = coll_reserve.to_assets(borrower_shares);
+        let borrower_liability = debt_reserve.to_asset_from_d_token(get_liabilities(&e, &debt_token, borrower.clone()));
+        let max_repay = mul_div_floor(borrower_liability, config.close_factor, SCALAR_7);
+        let repaid = if repay_amount > max_repay { max_repay } else { repay_amount };
+        let seized_value = mul_div_floor(repaid, SCALAR_7 + config.liq_bonus, SCALAR_7);
+        let mut seized = if debt_token == coll_token {
+            seized_value
+        } else {
+            mul_div_floor(seized_value, get_price(&e, &debt_token), get_price(&e, &coll_token))
+        };
+        if seized > borrower_collateral {
+            seized = borrower_collateral;
+        }
+
+        do_repay(&e, &debt_token, &liquidator_id, borrower.clone(), repaid);
+
+        // Move the seized collateral to the liquidator
+        let seized_shares = if seized == borrower_collateral { borrower_shares } else { coll_reserve.to_shares_up(seized) };
+        set_collateral(&e, &coll_token, borrower.clone(), borrower_shares - seized_shares);
+        let liquidator_shares = get_collateral(&e, &coll_token, liquidator_id.clone());
+        set_collateral(&e, &coll_token, liquidator_id.clone(), checked_add(&e, liquidator_shares, seized_shares));
+
+        events::liquidate(&e, borrower.clone(), liquidator_id, debt_token, coll_token, repaid, seized);
+
+        // Any liability left without collateral to back it is absorbed by depositors
+        if account_totals(&e, &borrower).0 == 0 {
+            write_off_liabilities(&e, &borrower);
+        }
+    }
+
+    fn flash_loan(e: Env, receiver: BytesN<32>, token: BytesN<32>, amount: i128, data: Bytes) {
+        require_positive_amount(&e, amount);
+        require_no_flash_loan(&e);
+        let token_client = get_token_client(&e, &token);
+        let cash = get_cash(&e, &token);
+        if cash < amount {
+            panic_error!(&e, PoolError::InsufficientLiquidity);
+        }
+        let fee = mul_div_ceil(amount, get_config(&e).flash_fee, SCALAR_7);
+
+        // Lock every change to the reserve, so the borrowed tokens can't be counted as paid back
+        // and the receiver can't move collateral or liabilities the reserve doesn't account for yet
+        e.data().set(DataKey::FlashLock, true);
+        token_client.xfer(
+            &Signature::Invoker,
+            &BigInt::zero(&e),
+            &Identifier::Contract(receiver.clone()),
+            &to_bigint(&e, amount)
+        );
+        FlashReceiverClient::new(&e, receiver.clone()).on_flash(&token, &amount, &fee, &data);
+        e.data().remove(DataKey::FlashLock);
+
+        if get_cash(&e, &token) < checked_add(&e, cash, fee) {
+            panic_error!(&e, PoolError::FlashLoanNotRepaid);
+        }
+        // Load the reserve only once the loan is back, accruing against the cash from before it
+        let mut reserve = load_reserve_with_cash(&e, &token, cash);
+        reserve.distribute(fee);
+        set_reserve_data(&e, &token, &reserve);
+        events::flash_loan(&e, token, receiver, amount, fee);
+    }
+
+    fn claim_fees(e: Env, sig: Signature, token: BytesN<32>, to: Identifier) {
+        let signer_id = sig.identifier(&e);
+        let nonce = auth_nonce(&e, &sig, &signer_id);
+
+        verify(&e, &sig, symbol!("claim_fees"), (&signer_id, &nonce, &token, &to));
+        verify_and_consume_nonce(&e, &sig, &nonce);
+        require_admin(&e, &signer_id);
+        require_no_flash_loan(&e);
+
+        let mut reserve = load_reserve(&e, &token);
+        let amount = reserve.fees;
+        if amount <= 0 {
+            return;
+        }
+        require_liquidity(&e, &token, amount);
+        reserve.fees = 0;
+        set_reserve_data(&e, &token, &reserve);
+
+        let token_client = get_token_client(&e, &token);
+        token_client.xfer(&Signature::Invoker, &BigInt::zero(&e), &to, &to_bigint(&e, amount));
+        events::fees_claimed(&e, token, signer_id, to, amount);
     }
 
     // ***** View *****
 
-    fn get_token(e: Env) -> BytesN<32> {
-        get_token_id(&e)
+    fn reserves(e: Env) -> Vec<BytesN<32>> {
+        require_initialized(&e);
+        get_reserves(&e)
+    }
+
+    fn collateral(e: Env, token: BytesN<32>, id: Identifier) -> i128 {
+        load_reserve(&e, &token).to_assets(get_collateral(&e, &token, id))
+    }
+
+    fn shares(e: Env, token: BytesN<32>, id: Identifier) -> i128 {
+        require_reserve(&e, &token);
+        get_collateral(&e, &token, id)
+    }
+
+    fn tot_shares(e: Env, token: BytesN<32>) -> i128 {
+        require_reserve(&e, &token);
+        get_reserve_data(&e, &token).s_supply
+    }
+
+    fn tot_coll(e: Env, token: BytesN<32>) -> i128 {
+        let reserve = load_reserve(&e, &token);
+        reserve.to_assets(reserve.s_supply)
+    }
+
+    fn tot_liab(e: Env, token: BytesN<32>) -> i128 {
+        let reserve = load_reserve(&e, &token);
+        reserve.to_asset_from_d_token(reserve.d_supply)
+    }
+
+    fn avail_liq(e: Env, token: BytesN<32>) -> i128 {
+        get_cash(&e, &token)
+    }
+
+    fn util_rate(e: Env, token: BytesN<32>) -> i128 {
+        let cash = get_cash(&e, &token);
+        let reserve = load_reserve_with_cash(&e, &token, cash);
+        utilization(reserve.to_asset_from_d_token(reserve.d_supply), cash)
+    }
+
+    fn exch_rate(e: Env, token: BytesN<32>) -> i128 {
+        load_reserve(&e, &token).s_rate
+    }
+
+    fn liability(e: Env, token: BytesN<32>, id: Identifier) -> i128 {
+        load_reserve(&e, &token).to_asset_from_d_token(get_liabilities(&e, &token, id))
     }
 
-    fn collateral(e: Env, id: Identifier) -> i64 {
-        get_collateral(&e, id)
+    fn health(e: Env, id: Identifier) -> i128 {
+        require_initialized(&e);
+        let (collateral, liability) = account_totals(&e, &id);
+        health_factor(collateral, liability, get_config(&e).max_ltv)
     }
 
-    fn liability(e: Env, id: Identifier) -> i64 {
-        get_liabilities(&e, id)
+    fn get_config(e: Env) -> PoolConfig {
+        require_initialized(&e);
+        get_config(&e)
+    }
+
+    fn res_config(e: Env, token: BytesN<32>) -> ReserveConfig {
+        require_reserve(&e, &token);
+        get_reserve_config(&e, &token)
+    }
+
+    fn caps(e: Env, token: BytesN<32>) -> ReserveCaps {
+        require_reserve(&e, &token);
+        get_caps(&e, &token)
+    }
+
+    fn get_oracle(e: Env) -> OracleConfig {
+        require_initialized(&e);
+        match get_oracle(&e) {
+            Some(config) => config,
+            None => panic_error!(&e, PoolError::NoOracle),
+        }
+    }
+
+    fn fees(e: Env, token: BytesN<32>) -> i128 {
+        load_reserve(&e, &token).fees
+    }
+
+    fn borrow_rate(e: Env, token: BytesN<32>) -> i128 {
+        let reserve = load_reserve(&e, &token);
+        let liabilities = reserve.to_asset_from_d_token(reserve.d_supply);
+        get_reserve_config(&e, &token).rate_model.borrow_rate(utilization(liabilities, get_cash(&e, &token)))
     }
 
     fn nonce(e: Env, id: Identifier) -> i64 {
         get_nonce(&e, &id)
     }
+
+    fn nonce_used(e: Env, id: Identifier, nonce: i64) -> bool {
+        is_nonce_used(&e, &id, nonce)
+    }
+
+    fn remaining(e: Env, owner: Identifier, nonce: i64, receiver: Identifier, token: BytesN<32>, max_amount: i128, expiration: u64) -> i128 {
+        if expiration < e.ledger().timestamp() || is_nonce_used(&e, &owner, nonce) {
+            return 0;
+        }
+        let key = borrow_max_key(&e, &owner, nonce, &receiver, &token, max_amount, expiration);
+        let remaining = max_amount - get_drawn(&e, &key);
+        if remaining > 0 { remaining } else { 0 }
+    }
+
+    fn sess_left(e: Env, owner: Identifier, pubkey: BytesN<32>) -> i128 {
+        match get_session(&e, &owner, &pubkey) {
+            Some(session) if session.scope.expiration >= e.ledger().timestamp() => {
+                session.scope.max_amount - session.drawn
+            }
+            _ => 0,
+        }
+    }
+
+    fn del_liab(e: Env, delegator: Identifier, delegatee: Identifier, token: BytesN<32>) -> i128 {
+        let d_tokens = get_delegated_liabilities(&e, &token, delegator, delegatee);
+        load_reserve(&e, &token).to_asset_from_d_token(d_tokens)
+    }
+
+    fn delegated(e: Env, delegator: Identifier, token: BytesN<32>) -> Map<Identifier, i128> {
+        let reserve = load_reserve(&e, &token);
+        let mut delegations: Map<Identifier, i128> = Map::new(&e);
+        for delegatee in get_delegatees(&e, &token, delegator.clone()).iter() {
+            let delegatee = delegatee.unwrap();
+            let d_tokens = get_delegated_liabilities(&e, &token, delegator.clone(), delegatee.clone());
+            delegations.set(delegatee, reserve.to_asset_from_d_token(d_tokens));
+        }
+        delegations
+    }
+
+    fn allowance(e: Env, delegator: Identifier, delegatee: Identifier, token: BytesN<32>) -> i128 {
+        get_borrow_allowance(&e, &token, delegator, delegatee)
+    }
 }
 
 // ****** Helpers *****
@@ -168,13 +1067,417 @@ fn get_contract_id(e: &Env) -> Identifier {
     Identifier::Contract(e.get_current_contract().into())
 }
 
-fn get_token_id(e: &Env) -> BytesN<32> {
-    let key = DataKey::Token;
-    e.data().get::<DataKey, BytesN<32>>(key).unwrap().unwrap()
+fn require_initialized(e: &Env) {
+    if !e.data().has(DataKey::Admin) {
+        panic_error!(e, PoolError::NotInitialized);
+    }
 }
 
- fn get_token_client(e: &Env) -> crate::token::Client {
-    let id = get_token_id(e);
-    crate::token::Client::new(e, id)
+/// Verify the pool has a reserve for the token
+fn require_reserve(e: &Env, token: &BytesN<32>) {
+    require_initialized(e);
+    if !e.data().has(DataKey::ResConfig(token.clone())) {
+        panic_error!(e, PoolError::ReserveNotFound);
+    }
+}
+
+ fn get_token_client(e: &Env, token: &BytesN<32>) -> crate::token::Client {
+    require_reserve(e, token);
+    crate::token::Client::new(e, token.clone())
  }
 
+/// Verify the signer is the admin of the pool
+fn require_admin(e: &Env, signer_id: &Identifier) {
+    require_initialized(e);
+    if *signer_id != get_admin(e) {
+        panic_error!(e, PoolError::NotAuthorized);
+    }
+}
+
+/// Verify an amount is positive and small enough to account for without overflowing
+fn require_positive_amount(e: &Env, amount: i128) {
+    if amount <= 0 {
+        panic_error!(e, PoolError::InvalidAmount);
+    }
+    if amount > MAX_AMOUNT {
+        panic_error!(e, PoolError::Overflow);
+    }
+}
+
+fn require_not_expired(e: &Env, expiration: u64) {
+    if expiration < e.ledger().timestamp() {
+        panic_error!(e, PoolError::ExpiredSignature);
+    }
+}
+
+/// Get the public key of a session key, which must be an ed25519 key
+fn session_pubkey(e: &Env, session_id: &Identifier) -> BytesN<32> {
+    match session_id {
+        Identifier::Ed25519(pubkey) => pubkey.clone(),
+        _ => panic_error!(e, PoolError::NotAuthorized),
+    }
+}
+
+/// Verify a borrow falls within the scope of a session key and count it towards the maximum amount
+fn use_session(e: &Env, owner: &Identifier, pubkey: &BytesN<32>, receiver: &Identifier, token: &BytesN<32>, amount: i128) {
+    let mut session = match get_session(e, owner, pubkey) {
+        Some(session) => session,
+        None => panic_error!(e, PoolError::NotAuthorized),
+    };
+    if session.scope.expiration < e.ledger().timestamp() {
+        panic_error!(e, PoolError::ExpiredSignature);
+    }
+    if session.scope.token != *token {
+        panic_error!(e, PoolError::NotAuthorized);
+    }
+    let mut allowed = false;
+    for id in session.scope.receivers.iter() {
+        if id.unwrap() == *receiver {
+            allowed = true;
+        }
+    }
+    if !allowed {
+        panic_error!(e, PoolError::NotAuthorized);
+    }
+
+    session.drawn = checked_add(e, session.drawn, amount);
+    if session.drawn > session.scope.max_amount {
+        panic_error!(e, PoolError::MaxAmountExceeded);
+    }
+    set_session(e, owner, pubkey, &session);
+}
+
+/// Add `n` to an ordered nonce. The result must leave room for one more nonce, otherwise
+/// the signer could never consume it and would be locked out of signing.
+fn checked_add_nonce(e: &Env, nonce: i64, n: i64) -> i64 {
+    match nonce.checked_add(n) {
+        Some(next) if next < i64::MAX => next,
+        _ => panic_error!(e, PoolError::InvalidNonceRange),
+    }
+}
+
+/// Verify the current ledger falls within the validity window of a signature
+fn require_valid(e: &Env, validity: &Validity) {
+    let (window, now) = match validity {
+        Validity::Time(window) => (window, e.ledger().timestamp()),
+        Validity::Sequence(window) => (window, u64::from(e.ledger().sequence())),
+    };
+    if now < window.valid_after {
+        panic_error!(e, PoolError::NotYetValid);
+    }
+    if window.expiration < now {
+        panic_error!(e, PoolError::ExpiredSignature);
+    }
+}
+
+/// Verify no flash loan is outstanding
+fn require_no_flash_loan(e: &Env) {
+    if e.data().has(DataKey::FlashLock) {
+        panic_error!(e, PoolError::FlashLoanActive);
+    }
+}
+
+/// Hash the payload of a `borrow_max` signature to key the tokens borrowed against it
+fn borrow_max_key(
+    e: &Env,
+    owner: &Identifier,
+    nonce: i64,
+    receiver: &Identifier,
+    token: &BytesN<32>,
+    max_amount: i128,
+    expiration: u64,
+) -> BytesN<32> {
+    let payload = (owner.clone(), nonce, receiver.clone(), token.clone(), max_amount, expiration);
+    e.compute_hash_sha256(&payload.serialize(e))
+}
+
+/// Verify the maximum loan-to-value is within (0, 1e7]
+fn require_valid_ltv(e: &Env, max_ltv: i128) {
+    if max_ltv <= 0 || max_ltv > SCALAR_7 {
+        panic_error!(e, PoolError::InvalidConfig);
+    }
+}
+
+/// The ratio of `collateral` at `max_ltv` to `liability`, scaled by 1e7
+fn health_factor(collateral: i128, liability: i128, max_ltv: i128) -> i128 {
+    if liability <= 0 {
+        return i128::MAX;
+    }
+    let borrowable = mul_div_floor(collateral, max_ltv, SCALAR_7);
+    mul_div_floor(borrowable, SCALAR_7, liability)
+}
+
+/// Verify the pool holds enough tokens of a reserve to send `amount` out
+fn require_liquidity(e: &Env, token: &BytesN<32>, amount: i128) {
+    if get_cash(e, token) < amount {
+        panic_error!(e, PoolError::InsufficientLiquidity);
+    }
+}
+
+/// Fetch the tokens of a reserve held by the pool
+fn get_cash(e: &Env, token: &BytesN<32>) -> i128 {
+    from_bigint(e, &get_token_client(e, token).balance(&get_contract_id(e)))
+}
+
+/// Load a reserve with interest accrued up to the current ledger timestamp. The
+/// result is not written back, callers that modify state are expected to store it.
+fn load_reserve(e: &Env, token: &BytesN<32>) -> ReserveData {
+    load_reserve_with_cash(e, token, get_cash(e, token))
+}
+
+/// Load a reserve with interest accrued up to the current ledger timestamp, given
+/// the tokens of the reserve held by the pool
+fn load_reserve_with_cash(e: &Env, token: &BytesN<32>, cash: i128) -> ReserveData {
+    let mut reserve = get_reserve_data(e, token);
+    let liabilities = reserve.to_asset_from_d_token(reserve.d_supply);
+    let rate = get_reserve_config(e, token).rate_model.borrow_rate(utilization(liabilities, cash));
+    reserve.accrue(e, rate, get_config(e).reserve_factor, e.ledger().timestamp());
+    reserve
+}
+
+/// Fetch the price of a token from the oracle, scaled by 1e7. Fails if no oracle is set
+/// or the price is older than the staleness limit.
+fn get_price(e: &Env, token: &BytesN<32>) -> i128 {
+    let config = match get_oracle(e) {
+        Some(config) => config,
+        None => panic_error!(e, PoolError::NoOracle),
+    };
+    let data = OracleClient::new(e, config.oracle).price(token);
+    if data.price <= 0 || data.price > SCALAR_9 * SCALAR_9 {
+        panic_error!(e, PoolError::InvalidPrice);
+    }
+    if e.ledger().timestamp() > data.timestamp.saturating_add(config.max_age) {
+        panic_error!(e, PoolError::StalePrice);
+    }
+    data.price
+}
+
+/// Sum the collateral and liabilities of `id` in every reserve. Positions in a single reserve
+/// are summed in tokens of that reserve, positions in several reserves are valued with oracle
+/// prices, rounding in favor of the pool. Without any liability nothing is priced, so the
+/// collateral is summed in tokens of each reserve.
+fn account_totals(e: &Env, id: &Identifier) -> (i128, i128) {
+    let mut tokens: Vec<BytesN<32>> = Vec::new(e);
+    let mut has_liability = false;
+    for token in get_reserves(e).iter() {
+        let token = token.unwrap();
+        let d_tokens = get_liabilities(e, &token, id.clone());
+        if d_tokens != 0 {
+            has_liability = true;
+        }
+        if get_collateral(e, &token, id.clone()) != 0 || d_tokens != 0 {
+            tokens.push_back(token);
+        }
+    }
+    // Collateral with nothing to cover can't fall short, so depositors without debt don't
+    // depend on the oracle
+    let priced = has_liability && tokens.len() > 1;
+
+    let mut collateral = 0;
+    let mut liability = 0;
+    for token in tokens.iter() {
+        let token = token.unwrap();
+        let reserve = load_reserve(e, &token);
+        let token_collateral = reserve.to_assets(get_collateral(e, &token, id.clone()));
+        let token_liability = reserve.to_asset_from_d_token(get_liabilities(e, &token, id.clone()));
+        if priced {
+            let price = get_price(e, &token);
+            collateral = checked_add_value(e, collateral, mul_div_floor(token_collateral, price, SCALAR_7));
+            liability = checked_add_value(e, liability, mul_div_ceil(token_liability, price, SCALAR_7));
+        } else {
+            collateral = checked_add(e, collateral, token_collateral);
+            liability = checked_add(e, liability, token_liability);
+        }
+    }
+    (collateral, liability)
+}
+
+/// Compute `x + y` for oracle-priced values, which unlike token amounts are not bound by `MAX_AMOUNT`
+fn checked_add_value(e: &Env, x: i128, y: i128) -> i128 {
+    match x.checked_add(y) {
+        Some(sum) => sum,
+        None => panic_error!(e, PoolError::Overflow),
+    }
+}
+
+/// Verify the collateral of `id` at the maximum loan-to-value still covers their liabilities
+fn require_healthy(e: &Env, id: &Identifier) {
+    let (collateral, liability) = account_totals(e, id);
+    if mul_div_floor(collateral, get_config(e).max_ltv, SCALAR_7) < liability {
+        panic_error!(e, PoolError::NotEnoughCollateral);
+    }
+}
+
+/// Remove every liability of `id` from the pool, spreading the loss of each reserve
+/// across the depositors of that reserve
+fn write_off_liabilities(e: &Env, id: &Identifier) {
+    for token in get_reserves(e).iter() {
+        let token = token.unwrap();
+        let d_tokens = get_liabilities(e, &token, id.clone());
+        if d_tokens == 0 {
+            continue;
+        }
+        let mut reserve = load_reserve(e, &token);
+        let bad_debt = reserve.to_asset_from_d_token(d_tokens);
+        set_liabilities(e, &token, id.clone(), 0);
+        cap_delegated_liabilities(e, &token, id, 0);
+        reserve.d_supply -= d_tokens;
+        reserve.socialize_loss(bad_debt);
+        set_reserve_data(e, &token, &reserve);
+        events::bad_debt(e, token, id.clone(), bad_debt);
+    }
+}
+
+/// Reduce the liabilities attributed to the delegatees of `delegator` until they no longer
+/// exceed the `d_tokens` the delegator still owes, taking from the oldest delegations first
+fn cap_delegated_liabilities(e: &Env, token: &BytesN<32>, delegator: &Identifier, d_tokens: i128) {
+    let delegatees = get_delegatees(e, token, delegator.clone());
+    let mut excess = -d_tokens;
+    for delegatee in delegatees.iter() {
+        excess += get_delegated_liabilities(e, token, delegator.clone(), delegatee.unwrap());
+    }
+    for delegatee in delegatees.iter() {
+        if excess <= 0 {
+            break;
+        }
+        let delegatee = delegatee.unwrap();
+        let delegated_d_tokens = get_delegated_liabilities(e, token, delegator.clone(), delegatee.clone());
+        let cut = if excess > delegated_d_tokens { delegated_d_tokens } else { excess };
+        set_delegated_liabilities(e, token, delegator.clone(), delegatee, delegated_d_tokens - cut);
+        excess -= cut;
+    }
+}
+
+/// Mint shares of a reserve for `amount` deposited tokens to `owner`, returning the new collateral of `owner`
+fn credit_collateral(e: &Env, token: &BytesN<32>, owner: Identifier, amount: i128) -> i128 {
+    require_no_flash_loan(e);
+
+    // The deposited tokens are already held by the pool, so accrue against the utilization
+    // from before the deposit
+    let mut reserve = load_reserve_with_cash(e, token, get_cash(e, token) - amount);
+
+    let shares = reserve.to_shares(amount);
+    if shares <= 0 {
+        panic_error!(e, PoolError::InvalidAmount);
+    }
+    let new_shares = checked_add(e, get_collateral(e, token, owner.clone()), shares);
+    set_collateral(e, token, owner, new_shares);
+    reserve.s_supply = checked_add(e, reserve.s_supply, shares);
+    set_reserve_data(e, token, &reserve);
+
+    let caps = get_caps(e, token);
+    if caps.supply_cap > 0 && reserve.to_assets(reserve.s_supply) > caps.supply_cap {
+        panic_error!(e, PoolError::SupplyCapExceeded);
+    }
+
+    reserve.to_assets(new_shares)
+}
+
+/// Add `amount` to the liabilities of `owner` and send the tokens to `receiver`
+fn do_borrow(e: &Env, token: &BytesN<32>, owner: Identifier, receiver: &Identifier, amount: i128) {
+    require_no_flash_loan(e);
+    let mut reserve = load_reserve(e, token);
+    require_liquidity(e, token, amount);
+
+    let owner_d_tokens = get_liabilities(e, token, owner.clone());
+    let d_tokens = reserve.to_d_token_up(amount);
+    let new_d_tokens = checked_add(e, owner_d_tokens, d_tokens);
+    set_liabilities(e, token, owner.clone(), new_d_tokens);
+    reserve.d_supply = checked_add(e, reserve.d_supply, d_tokens);
+    set_reserve_data(e, token, &reserve);
+
+    let caps = get_caps(e, token);
+    if caps.borrow_cap > 0 && reserve.to_asset_from_d_token(reserve.d_supply) > caps.borrow_cap {
+        panic_error!(e, PoolError::BorrowCapExceeded);
+    }
+    if caps.user_borrow_cap > 0 && reserve.to_asset_from_d_token(new_d_tokens) > caps.user_borrow_cap {
+        panic_error!(e, PoolError::UserBorrowCapExceeded);
+    }
+
+    // Check collateral and liability balances in every reserve, including the new liability.
+    // Failing reverts the liability recorded above.
+    require_healthy(e, &owner);
+
+    // Looks good - execute token distribution
+
+    // Attribute the liability to the receiver of the funds
+    if owner != *receiver {
+        let delegated_d_tokens = get_delegated_liabilities(e, token, owner.clone(), receiver.clone());
+        set_delegated_liabilities(e, token, owner.clone(), receiver.clone(), checked_add(e, delegated_d_tokens, d_tokens));
+    }
+
+    let token_client = get_token_client(e, token);
+    token_client.xfer(&Signature::Invoker, &BigInt::zero(e), receiver, &to_bigint(e, amount));
+
+    events::borrow(e, token.clone(), owner, receiver.clone(), amount, reserve.to_asset_from_d_token(new_d_tokens));
+}
+
+/// Debit `amount` from the collateral of `owner` and send the tokens to `receiver`
+fn do_withdraw(e: &Env, token: &BytesN<32>, owner: Identifier, receiver: &Identifier, amount: i128) {
+    require_no_flash_loan(e);
+    let mut reserve = load_reserve(e, token);
+
+    let owner_shares = get_collateral(e, token, owner.clone());
+    let burn_shares = reserve.to_shares_up(amount);
+    if burn_shares > owner_shares {
+        panic_error!(e, PoolError::NotEnoughCollateral);
+    }
+    require_liquidity(e, token, amount);
+
+    let new_shares = owner_shares - burn_shares;
+    set_collateral(e, token, owner.clone(), new_shares);
+    reserve.s_supply -= burn_shares;
+    set_reserve_data(e, token, &reserve);
+
+    // Check the remaining collateral in every reserve still covers the liabilities.
+    // Failing reverts the withdrawal recorded above.
+    require_healthy(e, &owner);
+
+    // Looks good - execute token distribution
+
+    let token_client = get_token_client(e, token);
+    token_client.xfer(&Signature::Invoker, &BigInt::zero(e), receiver, &to_bigint(e, amount));
+
+    events::withdraw(e, token.clone(), owner, receiver.clone(), amount, reserve.to_assets(new_shares));
+}
+
+/// Pull up to `amount` tokens from `from` and use them to pay down the liability of `beneficiary`
+fn do_repay(e: &Env, token: &BytesN<32>, from: &Identifier, beneficiary: Identifier, amount: i128) {
+    require_positive_amount(e, amount);
+    require_no_flash_loan(e);
+    let mut reserve = load_reserve(e, token);
+
+    let cur_d_tokens = get_liabilities(e, token, beneficiary.clone());
+    let cur_liability = reserve.to_asset_from_d_token(cur_d_tokens);
+    let repay_amount = if amount > cur_liability { cur_liability } else { amount };
+    if repay_amount <= 0 {
+        return;
+    }
+    // Burn every d_token on a full repayment so no rounding dust is left behind
+    let burn_d_tokens = if repay_amount == cur_liability { cur_d_tokens } else { reserve.to_d_token_down(repay_amount) };
+
+    let token_client = get_token_client(e, token);
+    token_client.xfer_from(
+        &Signature::Invoker,
+        &BigInt::zero(e),
+        from,
+        &get_contract_id(e),
+        &to_bigint(e, repay_amount)
+    );
+
+    let new_d_tokens = cur_d_tokens - burn_d_tokens;
+    set_liabilities(e, token, beneficiary.clone(), new_d_tokens);
+    reserve.d_supply -= burn_d_tokens;
+    set_reserve_data(e, token, &reserve);
+    events::repay(e, token.clone(), beneficiary.clone(), from.clone(), repay_amount, reserve.to_asset_from_d_token(new_d_tokens));
+
+    // Payments from a delegatee clear the liability attributed to them first
+    if *from != beneficiary {
+        let delegated_d_tokens = get_delegated_liabilities(e, token, beneficiary.clone(), from.clone());
+        if delegated_d_tokens > 0 {
+            let delegated_burn = if burn_d_tokens > delegated_d_tokens { delegated_d_tokens } else { burn_d_tokens };
+            set_delegated_liabilities(e, token, beneficiary.clone(), from.clone(), delegated_d_tokens - delegated_burn);
+        }
+    }
+    cap_delegated_liabilities(e, token, &beneficiary, new_d_tokens);
+}
@@ -1,8 +1,16 @@
-use soroban_auth_pool::{token, pool::{Pool, PoolClient}};
+#![allow(dead_code)]
+
+use soroban_auth_pool::{token, interest::{FixedRate, RateModel}, oracle::{MockOracle, MockOracleClient}, pool::{Pool, PoolClient, PoolConfig, ReserveConfig}};
 
 use rand::{thread_rng, RngCore};
-use soroban_sdk::{BytesN, Env, AccountId, IntoVal};
-use soroban_auth::Identifier;
+use soroban_sdk::{BigInt, BytesN, Env, AccountId, IntoVal, RawVal, Vec, testutils::Events};
+use soroban_auth::{Identifier, Signature};
+
+/// Convert an amount to the `BigInt` the token contract expects
+pub fn to_bigint(e: &Env, amount: i128) -> BigInt {
+    let high = BigInt::from_i64(e, (amount >> 64) as i64);
+    (high << 64) + BigInt::from_u64(e, amount as u64)
+}
 
 pub fn generate_contract_id(e: &Env) -> BytesN<32> {
     let mut id: [u8; 32] = Default::default();
@@ -30,3 +38,66 @@ pub fn create_pool_contract(e: &Env, contract_id: &BytesN<32>) -> PoolClient {
     return PoolClient::new(e, contract_id);
 }
 
+pub fn create_oracle_contract(e: &Env, contract_id: &BytesN<32>) -> MockOracleClient {
+    e.register_contract(contract_id, MockOracle {});
+    MockOracleClient::new(e, contract_id)
+}
+
+/// Mint `amount_i128` tokens to the user and deposit all of them into the pool
+#[allow(clippy::too_many_arguments)]
+pub fn mint_and_deposit(
+    e: &Env,
+    token_contract_id: &BytesN<32>,
+    token_client: &token::Client,
+    token_admin: &AccountId,
+    pool_client: &PoolClient,
+    pool_id: &Identifier,
+    user: &AccountId,
+    amount_i128: i128,
+) {
+    let amount = to_bigint(e, amount_i128);
+    token_client.with_source_account(token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(e),
+        &Identifier::Account(user.clone()),
+        &amount,
+    );
+    token_client.with_source_account(user).approve(
+        &Signature::Invoker,
+        &BigInt::zero(e),
+        pool_id,
+        &amount
+    );
+    pool_client.with_source_account(user).deposit(token_contract_id, &amount_i128);
+}
+
+/// Fetch the events published by the pool, ignoring any published by the token
+pub fn pool_events(e: &Env, pool_contract_id: &BytesN<32>) -> Vec<(BytesN<32>, Vec<RawVal>, RawVal)> {
+    let mut events = Vec::new(e);
+    for event in e.events().all().iter() {
+        let event = event.unwrap();
+        if event.0 == *pool_contract_id {
+            events.push_back(event);
+        }
+    }
+    events
+}
+
+/// A pool config with a 100% maximum loan-to-value, a 50% close factor, a 5% liquidation bonus
+/// and no fees
+pub fn pool_config() -> PoolConfig {
+    PoolConfig {
+        close_factor: 5000000,
+        liq_bonus: 500000,
+        max_ltv: 10000000,
+        flash_fee: 0,
+        reserve_factor: 0,
+    }
+}
+
+/// A reserve config with a fixed interest rate
+pub fn reserve_config(rate: i128) -> ReserveConfig {
+    ReserveConfig {
+        rate_model: RateModel::Fixed(FixedRate { rate }),
+    }
+}
@@ -0,0 +1,92 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, Env, testutils::Accounts};
+use soroban_auth::{Identifier, Signature};
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, to_bigint};
+
+#[test]
+fn test_amounts_beyond_i64() {
+    let e = Env::default();
+    let deposit_amount_i128 = 4 * i64::MAX as i128;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let borrow_amount_i128 = 3 * i64::MAX as i128;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+    token_client.with_source_account(&user1_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &deposit_amount_i128);
+    assert_eq!(pool_client.collateral(&token_contract_id, &user1_id), deposit_amount_i128);
+
+    let expiration = e.ledger().timestamp() + 100;
+    pool_client.with_source_account(&user1_acct).borrow_obo(&Signature::Invoker, &token_contract_id, &borrow_amount_i128, &expiration);
+
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), borrow_amount_i128);
+    assert_eq!(pool_client.avail_liq(&token_contract_id), deposit_amount_i128 - borrow_amount_i128);
+    assert_eq!(token_client.balance(&user1_id), to_bigint(&e, borrow_amount_i128));
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(26))")]
+fn test_amounts_overflow() {
+    let e = Env::default();
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    let user1_acct = e.accounts().generate_and_create();
+    pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &i128::MAX);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(10))")]
+fn test_amounts_negative() {
+    let e = Env::default();
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    let user1_acct = e.accounts().generate_and_create();
+    pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &-1);
+}
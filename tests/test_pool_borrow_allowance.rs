@@ -0,0 +1,142 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, Env, testutils::Accounts, symbol};
+use soroban_auth::{Identifier, Signature, testutils::ed25519};
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, to_bigint};
+
+/// TODO: Test support for accounts
+///       blocked by: https://github.com/stellar/rs-soroban-sdk/issues/741
+
+#[test]
+fn test_borrow_allowance_happy_path() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let allowance_i128 = 100000000;
+    let borrow_amount_i128 = 40000000;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    // grant a borrow allowance
+    let signer_nonce = pool_client.nonce(&user1_id);
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("approve_b"),
+        (&user1_id, &signer_nonce, &user2_id, &token_contract_id, &allowance_i128),
+    );
+    pool_client.approve_b(&sig, &user2_id, &token_contract_id, &allowance_i128);
+    assert_eq!(pool_client.allowance(&user1_id, &user2_id, &token_contract_id), allowance_i128);
+
+    // borrow twice against the allowance
+    pool_client.with_source_account(&user2_acct).borrow_a(&user1_id, &token_contract_id, &borrow_amount_i128);
+    pool_client.with_source_account(&user2_acct).borrow_a(&user1_id, &token_contract_id, &borrow_amount_i128);
+
+    assert_eq!(token_client.balance(&user2_id), to_bigint(&e, 2 * borrow_amount_i128));
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), 2 * borrow_amount_i128);
+    assert_eq!(pool_client.allowance(&user1_id, &user2_id, &token_contract_id), allowance_i128 - 2 * borrow_amount_i128);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(11))")]
+fn test_borrow_allowance_revoked() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let allowance_i128 = 100000000;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    // grant and then revoke a borrow allowance
+    let signer_nonce = pool_client.nonce(&user1_id);
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("approve_b"),
+        (&user1_id, &signer_nonce, &user2_id, &token_contract_id, &allowance_i128),
+    );
+    pool_client.approve_b(&sig, &user2_id, &token_contract_id, &allowance_i128);
+
+    let signer_nonce = pool_client.nonce(&user1_id);
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("revoke_b"),
+        (&user1_id, &signer_nonce, &user2_id, &token_contract_id),
+    );
+    pool_client.revoke_b(&sig, &user2_id, &token_contract_id);
+    assert_eq!(pool_client.allowance(&user1_id, &user2_id, &token_contract_id), 0);
+
+    pool_client.with_source_account(&user2_acct).borrow_a(&user1_id, &token_contract_id, &1);
+}
@@ -0,0 +1,200 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, Env, testutils::Accounts, symbol};
+use soroban_auth::{Identifier, Signature, testutils::ed25519};
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, to_bigint};
+
+#[test]
+fn test_borrow_max_partial_fills() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let max_amount_i128 = 3000;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    // sign once for up to the maximum amount
+    let expiration = e.ledger().timestamp() + 100;
+    let nonce: i64 = 7;
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_max"),
+        (&user1_id, &nonce, &user2_id, &token_contract_id, &max_amount_i128, &expiration),
+    );
+    assert_eq!(pool_client.remaining(&user1_id, &nonce, &user2_id, &token_contract_id, &max_amount_i128, &expiration), max_amount_i128);
+
+    // draw it down in pieces
+    pool_client.with_source_account(&user2_acct).borrow_max(&sig, &nonce, &token_contract_id, &max_amount_i128, &1000, &expiration);
+    assert_eq!(pool_client.remaining(&user1_id, &nonce, &user2_id, &token_contract_id, &max_amount_i128, &expiration), 2000);
+    pool_client.with_source_account(&user2_acct).borrow_max(&sig, &nonce, &token_contract_id, &max_amount_i128, &2000, &expiration);
+    assert_eq!(pool_client.remaining(&user1_id, &nonce, &user2_id, &token_contract_id, &max_amount_i128, &expiration), 0);
+
+    assert_eq!(token_client.balance(&user2_id), to_bigint(&e, max_amount_i128));
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), max_amount_i128);
+    assert!(!pool_client.nonce_used(&user1_id, &nonce));
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(27))")]
+fn test_borrow_max_exceeded() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let max_amount_i128 = 3000;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    let expiration = e.ledger().timestamp() + 100;
+    let nonce: i64 = 7;
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_max"),
+        (&user1_id, &nonce, &user2_id, &token_contract_id, &max_amount_i128, &expiration),
+    );
+
+    // the second draw goes past the maximum amount
+    pool_client.with_source_account(&user2_acct).borrow_max(&sig, &nonce, &token_contract_id, &max_amount_i128, &2000, &expiration);
+    pool_client.with_source_account(&user2_acct).borrow_max(&sig, &nonce, &token_contract_id, &max_amount_i128, &2000, &expiration);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(3))")]
+fn test_borrow_max_cancelled() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let max_amount_i128 = 3000;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    let expiration = e.ledger().timestamp() + 100;
+    let nonce: i64 = 7;
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_max"),
+        (&user1_id, &nonce, &user2_id, &token_contract_id, &max_amount_i128, &expiration),
+    );
+    pool_client.with_source_account(&user2_acct).borrow_max(&sig, &nonce, &token_contract_id, &max_amount_i128, &1000, &expiration);
+
+    // cancel the rest of the signature by invalidating its nonce
+    let signer_nonce = pool_client.nonce(&user1_id);
+    let (start, end): (i64, i64) = (nonce, nonce + 1);
+    let inval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("inval_nonc"),
+        (&user1_id, &signer_nonce, &start, &end),
+    );
+    pool_client.inval_nonc(&inval_sig, &start, &end);
+    assert_eq!(pool_client.remaining(&user1_id, &nonce, &user2_id, &token_contract_id, &max_amount_i128, &expiration), 0);
+
+    pool_client.with_source_account(&user2_acct).borrow_max(&sig, &nonce, &token_contract_id, &max_amount_i128, &1000, &expiration);
+}
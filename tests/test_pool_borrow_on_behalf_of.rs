@@ -4,7 +4,7 @@ use soroban_sdk::{BigInt, Env, testutils::{Accounts, Ledger, LedgerInfo}, symbol
 use soroban_auth::{Identifier, Signature, testutils::ed25519};
 
 mod helper;
-use helper::{create_token_contract, create_pool_contract, generate_contract_id};
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, to_bigint};
 
 /// TODO: Test support for accounts
 ///       blocked by: https://github.com/stellar/rs-soroban-sdk/issues/741
@@ -12,8 +12,8 @@ use helper::{create_token_contract, create_pool_contract, generate_contract_id};
 #[test]
 fn test_borrow_on_behalf_of_happy_path() {
     let e = Env::default();
-    let deposit_amount_i64 = 123456789;
-    let deposit_amount = BigInt::from_i64(&e, deposit_amount_i64);
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
 
     // deploy token contract
     let token_admin = e.accounts().generate_and_create();
@@ -24,7 +24,8 @@ fn test_borrow_on_behalf_of_happy_path() {
     let pool_contract_id = generate_contract_id(&e);
     let pool_id = Identifier::Contract(pool_contract_id.clone());
     let pool_client = create_pool_contract(&e, &pool_contract_id);
-    pool_client.initialize(&token_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
 
     // setup env
     let (user1_id, user1_sign) = ed25519::generate(&e);
@@ -48,10 +49,10 @@ fn test_borrow_on_behalf_of_happy_path() {
         symbol!("approve"),
         (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
     );
-    pool_client.deposit_p(&approval_sig, &deposit_amount.to_i64());
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
     assert_eq!(token_client.balance(&user1_id), BigInt::zero(&e));
     assert_eq!(token_client.balance(&pool_id), deposit_amount);
-    assert_eq!(pool_client.collateral(&user1_id), deposit_amount.to_i64());
+    assert_eq!(pool_client.collateral(&token_contract_id, &user1_id), deposit_amount_i128);
     println!("deposit with permit succesful");
 
     // borrow on behalf of
@@ -62,23 +63,23 @@ fn test_borrow_on_behalf_of_happy_path() {
         &user1_sign,
         &pool_contract_id,
         symbol!("borrow_obo"),
-        (&user1_id, &signer_nonce, &user2_id, &deposit_amount_i64, &expiration),
+        (&user1_id, &signer_nonce, &user2_id, &token_contract_id, &deposit_amount_i128, &expiration),
     );
-    pool_client.with_source_account(&user2_acct).borrow_obo(&sig, &deposit_amount_i64, &expiration);
+    pool_client.with_source_account(&user2_acct).borrow_obo(&sig, &token_contract_id, &deposit_amount_i128, &expiration);
 
     assert_eq!(token_client.balance(&user1_id), BigInt::zero(&e));
     assert_eq!(token_client.balance(&user2_id), deposit_amount);
     assert_eq!(token_client.balance(&pool_id), BigInt::zero(&e));
-    assert_eq!(pool_client.liability(&user1_id), deposit_amount_i64);
-    assert_eq!(pool_client.liability(&user2_id), 0);
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), deposit_amount_i128);
+    assert_eq!(pool_client.liability(&token_contract_id, &user2_id), 0);
 }
 
 #[test]
-#[should_panic(expected = "expired signature")] 
+#[should_panic(expected = "Status(ContractError(7))")] 
 fn test_borrow_on_behalf_of_invalid_expiration() {
     let e = Env::default();
-    let deposit_amount_i64 = 123456789;
-    let deposit_amount = BigInt::from_i64(&e, deposit_amount_i64);
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
 
     // deploy token contract
     let token_admin = e.accounts().generate_and_create();
@@ -89,7 +90,8 @@ fn test_borrow_on_behalf_of_invalid_expiration() {
     let pool_contract_id = generate_contract_id(&e);
     let pool_id = Identifier::Contract(pool_contract_id.clone());
     let pool_client = create_pool_contract(&e, &pool_contract_id);
-    pool_client.initialize(&token_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
 
     // setup env
     let (user1_id, user1_sign) = ed25519::generate(&e);
@@ -113,10 +115,10 @@ fn test_borrow_on_behalf_of_invalid_expiration() {
         symbol!("approve"),
         (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
     );
-    pool_client.deposit_p(&approval_sig, &deposit_amount.to_i64());
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
     assert_eq!(token_client.balance(&user1_id), BigInt::zero(&e));
     assert_eq!(token_client.balance(&pool_id), deposit_amount);
-    assert_eq!(pool_client.collateral(&user1_id), deposit_amount.to_i64());
+    assert_eq!(pool_client.collateral(&token_contract_id, &user1_id), deposit_amount_i128);
     println!("deposit with permit succesful");
 
     // borrow on behalf of
@@ -134,17 +136,17 @@ fn test_borrow_on_behalf_of_invalid_expiration() {
         &user1_sign,
         &pool_contract_id,
         symbol!("borrow_obo"),
-        (&user1_id, &signer_nonce, &user2_id, &deposit_amount_i64, &expiration),
+        (&user1_id, &signer_nonce, &user2_id, &token_contract_id, &deposit_amount_i128, &expiration),
     );
-    pool_client.with_source_account(&user2_acct).borrow_obo(&sig, &deposit_amount_i64, &expiration);
+    pool_client.with_source_account(&user2_acct).borrow_obo(&sig, &token_contract_id, &deposit_amount_i128, &expiration);
 }
 
 #[test]
-#[should_panic(expected = "not enough collateral")] 
+#[should_panic(expected = "Status(ContractError(8))")] 
 fn test_borrow_on_behalf_of_too_large() {
     let e = Env::default();
-    let deposit_amount_i64 = 123456789;
-    let deposit_amount = BigInt::from_i64(&e, deposit_amount_i64);
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
 
     // deploy token contract
     let token_admin = e.accounts().generate_and_create();
@@ -155,7 +157,8 @@ fn test_borrow_on_behalf_of_too_large() {
     let pool_contract_id = generate_contract_id(&e);
     let pool_id = Identifier::Contract(pool_contract_id.clone());
     let pool_client = create_pool_contract(&e, &pool_contract_id);
-    pool_client.initialize(&token_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
 
     // setup env
     let (user1_id, user1_sign) = ed25519::generate(&e);
@@ -179,10 +182,10 @@ fn test_borrow_on_behalf_of_too_large() {
         symbol!("approve"),
         (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
     );
-    pool_client.deposit_p(&approval_sig, &deposit_amount.to_i64());
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
     assert_eq!(token_client.balance(&user1_id), BigInt::zero(&e));
     assert_eq!(token_client.balance(&pool_id), deposit_amount);
-    assert_eq!(pool_client.collateral(&user1_id), deposit_amount.to_i64());
+    assert_eq!(pool_client.collateral(&token_contract_id, &user1_id), deposit_amount_i128);
     println!("deposit with permit succesful");
 
     // borrow on behalf of
@@ -193,7 +196,7 @@ fn test_borrow_on_behalf_of_too_large() {
         &user1_sign,
         &pool_contract_id,
         symbol!("borrow_obo"),
-        (&user1_id, &signer_nonce, &user2_id, &(deposit_amount_i64 + 1), &expiration),
+        (&user1_id, &signer_nonce, &user2_id, &token_contract_id, &(deposit_amount_i128 + 1), &expiration),
     );
-    pool_client.with_source_account(&user2_acct).borrow_obo(&sig, &(deposit_amount_i64 + 1), &expiration);
+    pool_client.with_source_account(&user2_acct).borrow_obo(&sig, &token_contract_id, &(deposit_amount_i128 + 1), &expiration);
 }
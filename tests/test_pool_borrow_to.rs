@@ -0,0 +1,190 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, Env, testutils::Accounts, symbol};
+use soroban_auth::{Identifier, Signature, testutils::ed25519};
+use soroban_auth_pool::pool::BorrowTo;
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, to_bigint};
+
+#[test]
+fn test_borrow_to_bearer() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let borrow_amount_i128 = 1000;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    // sign a voucher before the payee is known
+    let expiration = e.ledger().timestamp() + 100;
+    let nonce = pool_client.nonce(&user1_id);
+    let to = BorrowTo::Bearer;
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_to"),
+        (&user1_id, &nonce, &to, &token_contract_id, &borrow_amount_i128, &expiration),
+    );
+
+    // whoever holds the voucher redeems it
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+    pool_client.with_source_account(&user2_acct).borrow_to(&sig, &to, &token_contract_id, &borrow_amount_i128, &expiration);
+
+    assert_eq!(token_client.balance(&user2_id), to_bigint(&e, borrow_amount_i128));
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), borrow_amount_i128);
+    assert_eq!(pool_client.del_liab(&user1_id, &user2_id, &token_contract_id), borrow_amount_i128);
+    assert_eq!(pool_client.nonce(&user1_id), nonce + 1);
+}
+
+#[test]
+fn test_borrow_to_named_receiver() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let borrow_amount_i128 = 1000;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+    let user3_acct = e.accounts().generate_and_create();
+    let user3_id = Identifier::Account(user3_acct.clone());
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    // sign for user3 and hand the signature to user2 to relay
+    let expiration = e.ledger().timestamp() + 100;
+    let nonce = pool_client.nonce(&user1_id);
+    let to = BorrowTo::Named(user3_id.clone());
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_to"),
+        (&user1_id, &nonce, &to, &token_contract_id, &borrow_amount_i128, &expiration),
+    );
+    pool_client.with_source_account(&user2_acct).borrow_to(&sig, &to, &token_contract_id, &borrow_amount_i128, &expiration);
+
+    assert_eq!(token_client.balance(&user2_id), BigInt::zero(&e));
+    assert_eq!(token_client.balance(&user3_id), to_bigint(&e, borrow_amount_i128));
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), borrow_amount_i128);
+    assert_eq!(pool_client.del_liab(&user1_id, &user3_id, &token_contract_id), borrow_amount_i128);
+}
+
+#[test]
+#[should_panic(expected = "Failed ED25519 verification")]
+fn test_borrow_to_named_redeemed_as_bearer() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let borrow_amount_i128 = 1000;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user3_acct = e.accounts().generate_and_create();
+    let user3_id = Identifier::Account(user3_acct.clone());
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    // the relayer tries to keep the tokens meant for user3
+    let expiration = e.ledger().timestamp() + 100;
+    let nonce = pool_client.nonce(&user1_id);
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_to"),
+        (&user1_id, &nonce, &BorrowTo::Named(user3_id), &token_contract_id, &borrow_amount_i128, &expiration),
+    );
+    pool_client.with_source_account(&user2_acct).borrow_to(&sig, &BorrowTo::Bearer, &token_contract_id, &borrow_amount_i128, &expiration);
+}
@@ -0,0 +1,215 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, Env, testutils::Accounts, symbol};
+use soroban_auth::{Identifier, Signature, testutils::ed25519};
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, to_bigint};
+
+/// TODO: Test support for accounts
+///       blocked by: https://github.com/stellar/rs-soroban-sdk/issues/741
+
+#[test]
+fn test_borrow_unordered_happy_path() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let borrow_amount_i128 = 1000;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+    let user3_acct = e.accounts().generate_and_create();
+    let user3_id = Identifier::Account(user3_acct.clone());
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    // sign for two delegatees at once
+    let expiration = e.ledger().timestamp() + 100;
+    let user2_nonce: i64 = 70;
+    let user2_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_un"),
+        (&user1_id, &user2_nonce, &user2_id, &token_contract_id, &borrow_amount_i128, &expiration),
+    );
+    let user3_nonce: i64 = 3;
+    let user3_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_un"),
+        (&user1_id, &user3_nonce, &user3_id, &token_contract_id, &borrow_amount_i128, &expiration),
+    );
+
+    // both signatures remain valid regardless of the order they are used in
+    pool_client.with_source_account(&user2_acct).borrow_un(&user2_sig, &user2_nonce, &token_contract_id, &borrow_amount_i128, &expiration);
+    pool_client.with_source_account(&user3_acct).borrow_un(&user3_sig, &user3_nonce, &token_contract_id, &borrow_amount_i128, &expiration);
+
+    assert_eq!(token_client.balance(&user2_id), to_bigint(&e, borrow_amount_i128));
+    assert_eq!(token_client.balance(&user3_id), to_bigint(&e, borrow_amount_i128));
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), 2 * borrow_amount_i128);
+    assert!(pool_client.nonce_used(&user1_id, &user2_nonce));
+    assert!(pool_client.nonce_used(&user1_id, &user3_nonce));
+    assert!(!pool_client.nonce_used(&user1_id, &4));
+    assert_eq!(pool_client.nonce(&user1_id), 0);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(3))")]
+fn test_borrow_unordered_replay() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let borrow_amount_i128 = 1000;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    // borrow twice with the same signature
+    let expiration = e.ledger().timestamp() + 100;
+    let nonce: i64 = 12;
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_un"),
+        (&user1_id, &nonce, &user2_id, &token_contract_id, &borrow_amount_i128, &expiration),
+    );
+    pool_client.with_source_account(&user2_acct).borrow_un(&sig, &nonce, &token_contract_id, &borrow_amount_i128, &expiration);
+    pool_client.with_source_account(&user2_acct).borrow_un(&sig, &nonce, &token_contract_id, &borrow_amount_i128, &expiration);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(3))")]
+fn test_borrow_unordered_invalidated() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let borrow_amount_i128 = 1000;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    // sign a borrow and then invalidate a range that covers it
+    let expiration = e.ledger().timestamp() + 100;
+    let nonce: i64 = 100;
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_un"),
+        (&user1_id, &nonce, &user2_id, &token_contract_id, &borrow_amount_i128, &expiration),
+    );
+
+    let signer_nonce = pool_client.nonce(&user1_id);
+    let (start, end): (i64, i64) = (60, 130);
+    let inval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("inval_nonc"),
+        (&user1_id, &signer_nonce, &start, &end),
+    );
+    pool_client.inval_nonc(&inval_sig, &start, &end);
+    assert!(!pool_client.nonce_used(&user1_id, &59));
+    assert!(pool_client.nonce_used(&user1_id, &60));
+    assert!(pool_client.nonce_used(&user1_id, &129));
+    assert!(!pool_client.nonce_used(&user1_id, &130));
+
+    pool_client.with_source_account(&user2_acct).borrow_un(&sig, &nonce, &token_contract_id, &borrow_amount_i128, &expiration);
+}
@@ -0,0 +1,228 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, Env, testutils::{Accounts, Ledger, LedgerInfo}, symbol};
+use soroban_auth::{Identifier, Signature, testutils::ed25519};
+use soroban_auth_pool::pool::{Validity, ValidityWindow};
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, to_bigint};
+
+#[test]
+fn test_borrow_window_post_dated() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let borrow_amount_i128 = 1000;
+    e.ledger().set(LedgerInfo {
+        timestamp: 12345,
+        protocol_version: 1,
+        sequence_number: 10,
+        network_passphrase: Default::default(),
+        base_reserve: 10,
+    });
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    // sign a borrow that can only be used a day from now
+    let nonce: i64 = 42;
+    let validity = Validity::Time(ValidityWindow { valid_after: 12345 + 86400, expiration: 12345 + 2 * 86400 });
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_win"),
+        (&user1_id, &nonce, &user2_id, &token_contract_id, &borrow_amount_i128, &validity),
+    );
+
+    // the signer keeps using ordered nonces while the signature is scheduled
+    let signer_nonce = pool_client.nonce(&user1_id);
+    let (start, end): (i64, i64) = (0, 10);
+    let inval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("inval_nonc"),
+        (&user1_id, &signer_nonce, &start, &end),
+    );
+    pool_client.inval_nonc(&inval_sig, &start, &end);
+
+    e.ledger().set(LedgerInfo {
+        timestamp: 12345 + 86400,
+        protocol_version: 1,
+        sequence_number: 20,
+        network_passphrase: Default::default(),
+        base_reserve: 10,
+    });
+    pool_client.with_source_account(&user2_acct).borrow_win(&sig, &nonce, &token_contract_id, &borrow_amount_i128, &validity);
+
+    assert_eq!(token_client.balance(&user2_id), to_bigint(&e, borrow_amount_i128));
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), borrow_amount_i128);
+    assert!(pool_client.nonce_used(&user1_id, &nonce));
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(28))")]
+fn test_borrow_window_not_yet_valid() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let borrow_amount_i128 = 1000;
+    e.ledger().set(LedgerInfo {
+        timestamp: 12345,
+        protocol_version: 1,
+        sequence_number: 10,
+        network_passphrase: Default::default(),
+        base_reserve: 10,
+    });
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    // use a post-dated signature early
+    let nonce: i64 = 42;
+    let validity = Validity::Time(ValidityWindow { valid_after: 12345 + 86400, expiration: 12345 + 2 * 86400 });
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_win"),
+        (&user1_id, &nonce, &user2_id, &token_contract_id, &borrow_amount_i128, &validity),
+    );
+    pool_client.with_source_account(&user2_acct).borrow_win(&sig, &nonce, &token_contract_id, &borrow_amount_i128, &validity);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(7))")]
+fn test_borrow_window_sequence_expired() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let borrow_amount_i128 = 1000;
+    e.ledger().set(LedgerInfo {
+        timestamp: 12345,
+        protocol_version: 1,
+        sequence_number: 10,
+        network_passphrase: Default::default(),
+        base_reserve: 10,
+    });
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    // sign a borrow valid until ledger 15 and submit it in ledger 16
+    let nonce: i64 = 42;
+    let validity = Validity::Sequence(ValidityWindow { valid_after: 0, expiration: 15 });
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_win"),
+        (&user1_id, &nonce, &user2_id, &token_contract_id, &borrow_amount_i128, &validity),
+    );
+
+    e.ledger().set(LedgerInfo {
+        timestamp: 12345 + 5,
+        protocol_version: 1,
+        sequence_number: 16,
+        network_passphrase: Default::default(),
+        base_reserve: 10,
+    });
+    pool_client.with_source_account(&user2_acct).borrow_win(&sig, &nonce, &token_contract_id, &borrow_amount_i128, &validity);
+}
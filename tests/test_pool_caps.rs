@@ -0,0 +1,196 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, Env, testutils::Accounts};
+use soroban_auth::{Identifier, Signature};
+use soroban_auth_pool::pool::ReserveCaps;
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, to_bigint};
+
+#[test]
+fn test_caps_happy_path() {
+    let e = Env::default();
+    let deposit_amount_i128 = 1000000;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool with caps exactly at the amounts used
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+    let caps = ReserveCaps { supply_cap: deposit_amount_i128, borrow_cap: 600000, user_borrow_cap: 400000 };
+    pool_client.with_source_account(&token_admin).set_caps(&Signature::Invoker, &token_contract_id, &caps);
+    assert_eq!(pool_client.caps(&token_contract_id).user_borrow_cap, 400000);
+
+    // setup env
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+    token_client.with_source_account(&user1_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &deposit_amount_i128);
+
+    let expiration = e.ledger().timestamp() + 100;
+    pool_client.with_source_account(&user1_acct).borrow_obo(&Signature::Invoker, &token_contract_id, &400000, &expiration);
+
+    assert_eq!(pool_client.collateral(&token_contract_id, &user1_id), deposit_amount_i128);
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), 400000);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(23))")]
+fn test_caps_supply_cap_exceeded() {
+    let e = Env::default();
+    let deposit_amount_i128 = 1000000;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool with a supply cap below the deposit
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+    let caps = ReserveCaps { supply_cap: deposit_amount_i128 - 1, borrow_cap: 0, user_borrow_cap: 0 };
+    pool_client.with_source_account(&token_admin).set_caps(&Signature::Invoker, &token_contract_id, &caps);
+
+    // setup env
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+    token_client.with_source_account(&user1_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &deposit_amount_i128);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(24))")]
+fn test_caps_borrow_cap_exceeded() {
+    let e = Env::default();
+    let deposit_amount_i128 = 1000000;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool with a borrow cap shared by every borrower
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+    let caps = ReserveCaps { supply_cap: 0, borrow_cap: 500000, user_borrow_cap: 0 };
+    pool_client.with_source_account(&token_admin).set_caps(&Signature::Invoker, &token_contract_id, &caps);
+
+    // setup env
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+    token_client.with_source_account(&user1_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &deposit_amount_i128);
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user2_id,
+        &deposit_amount,
+    );
+    token_client.with_source_account(&user2_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&user2_acct).deposit(&token_contract_id, &deposit_amount_i128);
+
+    // each borrow is within the collateral of its borrower, together they exceed the cap
+    let expiration = e.ledger().timestamp() + 100;
+    pool_client.with_source_account(&user1_acct).borrow_obo(&Signature::Invoker, &token_contract_id, &300000, &expiration);
+    pool_client.with_source_account(&user2_acct).borrow_obo(&Signature::Invoker, &token_contract_id, &300000, &expiration);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(25))")]
+fn test_caps_user_borrow_cap_exceeded() {
+    let e = Env::default();
+    let deposit_amount_i128 = 1000000;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool with a per-identifier borrow cap
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+    let caps = ReserveCaps { supply_cap: 0, borrow_cap: 0, user_borrow_cap: 400000 };
+    pool_client.with_source_account(&token_admin).set_caps(&Signature::Invoker, &token_contract_id, &caps);
+
+    // setup env
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+    token_client.with_source_account(&user1_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &deposit_amount_i128);
+
+    // two borrows within the collateral that together exceed the per-identifier cap
+    let expiration = e.ledger().timestamp() + 100;
+    pool_client.with_source_account(&user1_acct).borrow_obo(&Signature::Invoker, &token_contract_id, &300000, &expiration);
+    pool_client.with_source_account(&user1_acct).borrow_obo(&Signature::Invoker, &token_contract_id, &300000, &expiration);
+}
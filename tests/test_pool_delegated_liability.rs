@@ -0,0 +1,201 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, Env, testutils::Accounts, symbol};
+use soroban_auth::{Identifier, Signature, testutils::ed25519};
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, to_bigint};
+
+/// TODO: Test support for accounts
+///       blocked by: https://github.com/stellar/rs-soroban-sdk/issues/741
+
+#[test]
+fn test_delegated_liability_borrow_and_repay() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let user2_borrow_i128 = 1000;
+    let user3_borrow_i128 = 2500;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+    let user3_acct = e.accounts().generate_and_create();
+    let user3_id = Identifier::Account(user3_acct.clone());
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    // borrow on behalf of user1 from two delegatees
+    let expiration = e.ledger().timestamp() + 100;
+    let signer_nonce = pool_client.nonce(&user1_id);
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_obo"),
+        (&user1_id, &signer_nonce, &user2_id, &token_contract_id, &user2_borrow_i128, &expiration),
+    );
+    pool_client.with_source_account(&user2_acct).borrow_obo(&sig, &token_contract_id, &user2_borrow_i128, &expiration);
+
+    let signer_nonce = pool_client.nonce(&user1_id);
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_obo"),
+        (&user1_id, &signer_nonce, &user3_id, &token_contract_id, &user3_borrow_i128, &expiration),
+    );
+    pool_client.with_source_account(&user3_acct).borrow_obo(&sig, &token_contract_id, &user3_borrow_i128, &expiration);
+
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), user2_borrow_i128 + user3_borrow_i128);
+    assert_eq!(pool_client.del_liab(&user1_id, &user2_id, &token_contract_id), user2_borrow_i128);
+    assert_eq!(pool_client.del_liab(&user1_id, &user3_id, &token_contract_id), user3_borrow_i128);
+    let delegations = pool_client.delegated(&user1_id, &token_contract_id);
+    assert_eq!(delegations.len(), 2);
+    assert_eq!(delegations.get_unchecked(user2_id.clone()).unwrap(), user2_borrow_i128);
+    assert_eq!(delegations.get_unchecked(user3_id.clone()).unwrap(), user3_borrow_i128);
+
+    // user2 repays their part of the liability
+    token_client.with_source_account(&user2_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &to_bigint(&e, user2_borrow_i128)
+    );
+    pool_client.with_source_account(&user2_acct).repay_for(&user1_id, &token_contract_id, &user2_borrow_i128);
+
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), user3_borrow_i128);
+    assert_eq!(pool_client.del_liab(&user1_id, &user2_id, &token_contract_id), 0);
+    assert_eq!(pool_client.del_liab(&user1_id, &user3_id, &token_contract_id), user3_borrow_i128);
+    let delegations = pool_client.delegated(&user1_id, &token_contract_id);
+    assert_eq!(delegations.len(), 1);
+    assert_eq!(delegations.get_unchecked(user3_id.clone()).unwrap(), user3_borrow_i128);
+}
+
+#[test]
+fn test_delegated_liability_repaid_by_third_party() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let user2_borrow_i128 = 1000;
+    let user3_borrow_i128 = 2500;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+    let user3_acct = e.accounts().generate_and_create();
+    let user3_id = Identifier::Account(user3_acct.clone());
+    let user4_acct = e.accounts().generate_and_create();
+    let user4_id = Identifier::Account(user4_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user4_id,
+        &to_bigint(&e, user2_borrow_i128 + user3_borrow_i128),
+    );
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    // borrow on behalf of user1 from two delegatees
+    let expiration = e.ledger().timestamp() + 100;
+    let signer_nonce = pool_client.nonce(&user1_id);
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_obo"),
+        (&user1_id, &signer_nonce, &user2_id, &token_contract_id, &user2_borrow_i128, &expiration),
+    );
+    pool_client.with_source_account(&user2_acct).borrow_obo(&sig, &token_contract_id, &user2_borrow_i128, &expiration);
+
+    let signer_nonce = pool_client.nonce(&user1_id);
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_obo"),
+        (&user1_id, &signer_nonce, &user3_id, &token_contract_id, &user3_borrow_i128, &expiration),
+    );
+    pool_client.with_source_account(&user3_acct).borrow_obo(&sig, &token_contract_id, &user3_borrow_i128, &expiration);
+
+    // user4 repays part of the liability, the oldest delegation is cleared first
+    token_client.with_source_account(&user4_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &to_bigint(&e, user2_borrow_i128 + user3_borrow_i128)
+    );
+    pool_client.with_source_account(&user4_acct).repay_for(&user1_id, &token_contract_id, &1500);
+
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), 2000);
+    assert_eq!(pool_client.del_liab(&user1_id, &user2_id, &token_contract_id), 0);
+    assert_eq!(pool_client.del_liab(&user1_id, &user3_id, &token_contract_id), 2000);
+    let delegations = pool_client.delegated(&user1_id, &token_contract_id);
+    assert_eq!(delegations.len(), 1);
+    assert_eq!(delegations.get_unchecked(user3_id.clone()).unwrap(), 2000);
+
+    // user4 repays the rest, no liability is left to attribute
+    pool_client.with_source_account(&user4_acct).repay_for(&user1_id, &token_contract_id, &2000);
+
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), 0);
+    assert_eq!(pool_client.del_liab(&user1_id, &user3_id, &token_contract_id), 0);
+    assert_eq!(pool_client.delegated(&user1_id, &token_contract_id).len(), 0);
+}
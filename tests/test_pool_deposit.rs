@@ -4,13 +4,13 @@ use soroban_sdk::{BigInt, Env, testutils::Accounts};
 use soroban_auth::{Identifier, Signature};
 
 mod helper;
-use helper::{create_token_contract, create_pool_contract, generate_contract_id};
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, to_bigint};
 
 #[test]
 fn test_deposit_happy_path() {
     let e = Env::default();
-    let deposit_amount_i64 = 123456789;
-    let deposit_amount = BigInt::from_i64(&e, deposit_amount_i64);
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
 
     // deploy token contract
     let token_admin = e.accounts().generate_and_create();
@@ -21,7 +21,8 @@ fn test_deposit_happy_path() {
     let pool_contract_id = generate_contract_id(&e);
     let pool_id = Identifier::Contract(pool_contract_id.clone());
     let pool_client = create_pool_contract(&e, &pool_contract_id);
-    pool_client.initialize(&token_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
 
     // setup env
     let user1_acct = e.accounts().generate_and_create();
@@ -42,9 +43,9 @@ fn test_deposit_happy_path() {
     );
 
     // deposit
-    pool_client.with_source_account(&user1_acct).deposit(&deposit_amount_i64);
+    pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &deposit_amount_i128);
 
     assert_eq!(token_client.balance(&user1_id), BigInt::zero(&e));
     assert_eq!(token_client.balance(&pool_id), deposit_amount);
-    assert_eq!(pool_client.collateral(&user1_id), deposit_amount_i64);
+    assert_eq!(pool_client.collateral(&token_contract_id, &user1_id), deposit_amount_i128);
 }
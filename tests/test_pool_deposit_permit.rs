@@ -4,7 +4,7 @@ use soroban_sdk::{BigInt, Env, testutils::Accounts, symbol};
 use soroban_auth::{Identifier, Signature, testutils::ed25519};
 
 mod helper;
-use helper::{create_token_contract, create_pool_contract, generate_contract_id};
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, to_bigint};
 
 /// TODO: Test support for accounts
 ///       blocked by: https://github.com/stellar/rs-soroban-sdk/issues/741
@@ -12,8 +12,8 @@ use helper::{create_token_contract, create_pool_contract, generate_contract_id};
 #[test]
 fn test_deposit_permit_happy_path() {
     let e = Env::default();
-    let deposit_amount_i64 = 123456789;
-    let deposit_amount = BigInt::from_i64(&e, deposit_amount_i64);
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
 
     // deploy token contract
     let token_admin = e.accounts().generate_and_create();
@@ -24,7 +24,8 @@ fn test_deposit_permit_happy_path() {
     let pool_contract_id = generate_contract_id(&e);
     let pool_id = Identifier::Contract(pool_contract_id.clone());
     let pool_client = create_pool_contract(&e, &pool_contract_id);
-    pool_client.initialize(&token_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
 
     // setup env
     let (user1_id, user1_sign) = ed25519::generate(&e);
@@ -45,19 +46,19 @@ fn test_deposit_permit_happy_path() {
         symbol!("approve"),
         (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
     );
-    pool_client.deposit_p(&approval_sig, &deposit_amount_i64);
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
 
     assert_eq!(token_client.balance(&user1_id), BigInt::zero(&e));
     assert_eq!(token_client.balance(&pool_id), deposit_amount);
-    assert_eq!(pool_client.collateral(&user1_id), deposit_amount_i64);
+    assert_eq!(pool_client.collateral(&token_contract_id, &user1_id), deposit_amount_i128);
 }
 
 #[test]
 #[should_panic(expected = "Failed ED25519 verification")]
 fn test_deposit_permit_bad_signature() {
     let e = Env::default();
-    let deposit_amount_i64 = 123456789;
-    let deposit_amount = BigInt::from_i64(&e, deposit_amount_i64);
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
 
     // deploy token contract
     let token_admin = e.accounts().generate_and_create();
@@ -68,7 +69,8 @@ fn test_deposit_permit_bad_signature() {
     let pool_contract_id = generate_contract_id(&e);
     let pool_id = Identifier::Contract(pool_contract_id.clone());
     let pool_client = create_pool_contract(&e, &pool_contract_id);
-    pool_client.initialize(&token_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
 
     // setup env
     let (user1_id, _user1_sign) = ed25519::generate(&e);
@@ -93,5 +95,5 @@ fn test_deposit_permit_bad_signature() {
     );
 
     // - call deposit with permit
-    pool_client.deposit_p(&approval_sig, &deposit_amount_i64);
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
 }
@@ -0,0 +1,144 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, Env, IntoVal, testutils::Accounts, symbol, vec};
+use soroban_auth::{Identifier, Signature, testutils::ed25519};
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, pool_events, to_bigint};
+
+#[test]
+fn test_events_deposit() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+    token_client.with_source_account(&user1_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+
+    // deposit
+    pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &deposit_amount_i128);
+
+    assert_eq!(
+        pool_events(&e, &pool_contract_id),
+        vec![
+            &e,
+            (
+                pool_contract_id.clone(),
+                (symbol!("add_res"), Identifier::Account(token_admin.clone())).into_val(&e),
+                token_contract_id.clone().into_val(&e)
+            ),
+            (
+                pool_contract_id.clone(),
+                (symbol!("deposit"), token_contract_id.clone(), user1_id.clone()).into_val(&e),
+                (deposit_amount_i128, deposit_amount_i128).into_val(&e)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_events_deposit_permit_and_borrow_on_behalf_of() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let borrow_amount_i128 = 23456789;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    // borrow on behalf of
+    let signer_nonce = pool_client.nonce(&user1_id);
+    let expiration = e.ledger().timestamp() + 100;
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_obo"),
+        (&user1_id, &signer_nonce, &user2_id, &token_contract_id, &borrow_amount_i128, &expiration),
+    );
+    pool_client.with_source_account(&user2_acct).borrow_obo(&sig, &token_contract_id, &borrow_amount_i128, &expiration);
+
+    assert_eq!(
+        pool_events(&e, &pool_contract_id),
+        vec![
+            &e,
+            (
+                pool_contract_id.clone(),
+                (symbol!("add_res"), Identifier::Account(token_admin.clone())).into_val(&e),
+                token_contract_id.clone().into_val(&e)
+            ),
+            (
+                pool_contract_id.clone(),
+                (symbol!("deposit_p"), token_contract_id.clone(), user1_id.clone()).into_val(&e),
+                (deposit_amount_i128, deposit_amount_i128).into_val(&e)
+            ),
+            (
+                pool_contract_id.clone(),
+                (symbol!("nonce"), user1_id.clone()).into_val(&e),
+                signer_nonce.into_val(&e)
+            ),
+            (
+                pool_contract_id.clone(),
+                (symbol!("borrow"), token_contract_id.clone(), user1_id.clone(), user2_id.clone()).into_val(&e),
+                (borrow_amount_i128, borrow_amount_i128).into_val(&e)
+            ),
+        ]
+    );
+}
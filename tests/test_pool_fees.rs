@@ -0,0 +1,115 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, Env, testutils::{Accounts, Ledger, LedgerInfo}};
+use soroban_auth::{Identifier, Signature};
+use soroban_auth_pool::pool::PoolConfig;
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, to_bigint};
+
+const ONE_YEAR: u64 = 31536000;
+
+#[test]
+fn test_fees_claimed_by_admin() {
+    let e = Env::default();
+    let deposit_amount_i128 = 2000000;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let borrow_amount_i128 = 1000000;
+    e.ledger().set(LedgerInfo {
+        timestamp: 12345,
+        protocol_version: 1,
+        sequence_number: 10,
+        network_passphrase: Default::default(),
+        base_reserve: 10,
+    });
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool with a 20% reserve factor and a 10% annual rate
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    let config = PoolConfig { reserve_factor: 2000000, ..pool_config() };
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &config);
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(1000000));
+
+    // setup env
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+    token_client.with_source_account(&user1_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &deposit_amount_i128);
+
+    // borrow against own collateral
+    let expiration = e.ledger().timestamp() + 100;
+    pool_client.with_source_account(&user1_acct).borrow_obo(&Signature::Invoker, &token_contract_id, &borrow_amount_i128, &expiration);
+
+    e.ledger().set(LedgerInfo {
+        timestamp: 12345 + ONE_YEAR,
+        protocol_version: 1,
+        sequence_number: 20,
+        network_passphrase: Default::default(),
+        base_reserve: 10,
+    });
+
+    // 100000 of interest, 20% of it set aside for the treasury
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), 1100000);
+    assert_eq!(pool_client.fees(&token_contract_id), 20000);
+    assert_eq!(pool_client.collateral(&token_contract_id, &user1_id), 2080000);
+
+    let treasury_acct = e.accounts().generate_and_create();
+    let treasury_id = Identifier::Account(treasury_acct.clone());
+    pool_client.with_source_account(&token_admin).claim_fees(&Signature::Invoker, &token_contract_id, &treasury_id);
+
+    assert_eq!(token_client.balance(&treasury_id), to_bigint(&e, 20000));
+    assert_eq!(pool_client.fees(&token_contract_id), 0);
+    assert_eq!(pool_client.collateral(&token_contract_id, &user1_id), 2080000);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(15))")]
+fn test_fees_claimed_by_non_admin() {
+    let e = Env::default();
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool with a 20% reserve factor
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    let config = PoolConfig { reserve_factor: 2000000, ..pool_config() };
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &config);
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(1000000));
+
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    pool_client.with_source_account(&user1_acct).claim_fees(&Signature::Invoker, &token_contract_id, &user1_id);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(13))")]
+fn test_fees_invalid_reserve_factor() {
+    let e = Env::default();
+
+    // deploy auth pool with every bit of interest going to the treasury
+    let token_admin = e.accounts().generate_and_create();
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    let config = PoolConfig { reserve_factor: 10000000, ..pool_config() };
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &config);
+}
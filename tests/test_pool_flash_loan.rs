@@ -0,0 +1,205 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, Bytes, BytesN, Env, testutils::Accounts};
+use soroban_auth::{Identifier, Signature};
+use soroban_auth_pool::{flash::{MockFlashReceiver, MockFlashReceiverClient, MockReentrantReceiver}, pool::PoolConfig};
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, to_bigint};
+
+fn create_receiver_contract(e: &Env, contract_id: &BytesN<32>) -> MockFlashReceiverClient {
+    e.register_contract(contract_id, MockFlashReceiver {});
+    MockFlashReceiverClient::new(e, contract_id)
+}
+
+#[test]
+fn test_flash_loan_happy_path() {
+    let e = Env::default();
+    let deposit_amount_i128 = 1000000;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let fee_i128 = 900;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool with a 0.09% flash loan fee
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    let config = PoolConfig { flash_fee: 9000, ..pool_config() };
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &config);
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // deploy receiver contract holding enough tokens to pay the fee
+    let receiver_contract_id = generate_contract_id(&e);
+    let receiver_id = Identifier::Contract(receiver_contract_id.clone());
+    let receiver_client = create_receiver_contract(&e, &receiver_contract_id);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &receiver_id,
+        &to_bigint(&e, fee_i128),
+    );
+
+    // setup env
+    let lender_acct = e.accounts().generate_and_create();
+    let lender_id = Identifier::Account(lender_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &lender_id,
+        &deposit_amount,
+    );
+    token_client.with_source_account(&lender_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&lender_acct).deposit(&token_contract_id, &deposit_amount_i128);
+
+    // borrow every token in the pool for the duration of the callback
+    let data = Bytes::from_array(&e, &[1, 2, 3]);
+    let user1_acct = e.accounts().generate_and_create();
+    pool_client.with_source_account(&user1_acct).flash_loan(&receiver_contract_id, &token_contract_id, &deposit_amount_i128, &data);
+
+    assert_eq!(receiver_client.last_data(), data);
+    assert_eq!(token_client.balance(&receiver_id), BigInt::zero(&e));
+    assert_eq!(token_client.balance(&pool_id), to_bigint(&e, deposit_amount_i128 + fee_i128));
+    assert_eq!(pool_client.collateral(&token_contract_id, &lender_id), deposit_amount_i128 + fee_i128);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(22))")]
+fn test_flash_loan_not_repaid() {
+    let e = Env::default();
+    let deposit_amount_i128 = 1000000;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool with a 0.09% flash loan fee
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    let config = PoolConfig { flash_fee: 9000, ..pool_config() };
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &config);
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // deploy receiver contract without any tokens to pay the fee
+    let receiver_contract_id = generate_contract_id(&e);
+    create_receiver_contract(&e, &receiver_contract_id);
+
+    // setup env
+    let lender_acct = e.accounts().generate_and_create();
+    let lender_id = Identifier::Account(lender_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &lender_id,
+        &deposit_amount,
+    );
+    token_client.with_source_account(&lender_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&lender_acct).deposit(&token_contract_id, &deposit_amount_i128);
+
+    let user1_acct = e.accounts().generate_and_create();
+    pool_client.with_source_account(&user1_acct).flash_loan(&receiver_contract_id, &token_contract_id, &deposit_amount_i128, &Bytes::new(&e));
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(9))")]
+fn test_flash_loan_more_than_liquidity() {
+    let e = Env::default();
+    let deposit_amount_i128 = 1000000;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // deploy receiver contract
+    let receiver_contract_id = generate_contract_id(&e);
+    create_receiver_contract(&e, &receiver_contract_id);
+
+    // setup env
+    let lender_acct = e.accounts().generate_and_create();
+    let lender_id = Identifier::Account(lender_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &lender_id,
+        &deposit_amount,
+    );
+    token_client.with_source_account(&lender_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&lender_acct).deposit(&token_contract_id, &deposit_amount_i128);
+
+    let user1_acct = e.accounts().generate_and_create();
+    pool_client.with_source_account(&user1_acct).flash_loan(&receiver_contract_id, &token_contract_id, &(deposit_amount_i128 + 1), &Bytes::new(&e));
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(21))")]
+fn test_flash_loan_reentrant_withdraw() {
+    let e = Env::default();
+    let deposit_amount_i128 = 1000000;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // deploy receiver contract that withdraws from the pool during the callback
+    let receiver_contract_id = generate_contract_id(&e);
+    e.register_contract(&receiver_contract_id, MockReentrantReceiver {});
+
+    // setup env
+    let lender_acct = e.accounts().generate_and_create();
+    let lender_id = Identifier::Account(lender_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &lender_id,
+        &deposit_amount,
+    );
+    token_client.with_source_account(&lender_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&lender_acct).deposit(&token_contract_id, &deposit_amount_i128);
+
+    let user1_acct = e.accounts().generate_and_create();
+    pool_client.with_source_account(&user1_acct).flash_loan(&receiver_contract_id, &token_contract_id, &1000, &Bytes::new(&e));
+}
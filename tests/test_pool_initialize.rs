@@ -0,0 +1,153 @@
+#![cfg(test)]
+
+use soroban_sdk::{Env, testutils::Accounts, vec};
+use soroban_auth::{Identifier, Signature};
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id};
+
+#[test]
+fn test_initialize_happy_path() {
+    let e = Env::default();
+
+    // deploy token contracts
+    let token_admin = e.accounts().generate_and_create();
+    let token_a_contract_id = generate_contract_id(&e);
+    create_token_contract(&e, &token_a_contract_id, &token_admin);
+    let token_b_contract_id = generate_contract_id(&e);
+    create_token_contract(&e, &token_b_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    assert_eq!(pool_client.reserves().len(), 0);
+
+    // add reserves
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_a_contract_id, &reserve_config(0));
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_b_contract_id, &reserve_config(1000000));
+
+    assert_eq!(pool_client.reserves(), vec![&e, token_a_contract_id.clone(), token_b_contract_id.clone()]);
+    assert_eq!(pool_client.tot_shares(&token_b_contract_id), 0);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(5))")]
+fn test_initialize_twice() {
+    let e = Env::default();
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(6))")]
+fn test_not_initialized() {
+    let e = Env::default();
+
+    // deploy auth pool without initializing it
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+
+    pool_client.reserves();
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(6))")]
+fn test_add_reserve_not_initialized() {
+    let e = Env::default();
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy auth pool without initializing it
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(6))")]
+fn test_liquidate_not_initialized() {
+    let e = Env::default();
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy auth pool without initializing it
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+
+    let user1_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(e.accounts().generate_and_create());
+    pool_client.with_source_account(&user1_acct).liquidate(&user2_id, &token_contract_id, &token_contract_id, &100);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(15))")]
+fn test_add_reserve_by_non_admin() {
+    let e = Env::default();
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+
+    let user1_acct = e.accounts().generate_and_create();
+    pool_client.with_source_account(&user1_acct).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(16))")]
+fn test_reserve_not_found() {
+    let e = Env::default();
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool without a reserve for the token
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+
+    let user1_acct = e.accounts().generate_and_create();
+    pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &100);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(17))")]
+fn test_add_reserve_twice() {
+    let e = Env::default();
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+}
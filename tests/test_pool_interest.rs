@@ -0,0 +1,202 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, Env, testutils::{Accounts, Ledger, LedgerInfo}};
+use soroban_auth::{Identifier, Signature};
+use soroban_auth_pool::{interest::{KinkedRate, LinearRate, RateModel}, pool::ReserveConfig};
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, to_bigint};
+
+const ONE_YEAR: u64 = 31536000;
+
+/// Deposit `deposit_amount` and borrow `borrow_amount` against it from a single account, then
+/// advance the ledger one year and return the liability of the account
+fn liability_after_one_year(config: ReserveConfig, deposit_amount_i128: i128, borrow_amount_i128: i128) -> i128 {
+    let e = Env::default();
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    e.ledger().set(LedgerInfo {
+        timestamp: 12345,
+        protocol_version: 1,
+        sequence_number: 10,
+        network_passphrase: Default::default(),
+        base_reserve: 10,
+    });
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &config);
+
+    // setup env
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+    token_client.with_source_account(&user1_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &deposit_amount_i128);
+
+    // borrow against own collateral
+    let expiration = e.ledger().timestamp() + 100;
+    pool_client.with_source_account(&user1_acct).borrow_obo(&Signature::Invoker, &token_contract_id, &borrow_amount_i128, &expiration);
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), borrow_amount_i128);
+
+    e.ledger().set(LedgerInfo {
+        timestamp: 12345 + ONE_YEAR,
+        protocol_version: 1,
+        sequence_number: 20,
+        network_passphrase: Default::default(),
+        base_reserve: 10,
+    });
+    pool_client.liability(&token_contract_id, &user1_id)
+}
+
+/// Deposit and borrow from a single account at a fixed 5% rate, then advance the ledger `steps`
+/// times by `step` seconds, touching the reserve with a deposit after each step, and return the
+/// liability of the account
+fn liability_after_steps(steps: u64, step: u64) -> i128 {
+    let e = Env::default();
+    let deposit_amount_i128 = 2000000000000;
+    let borrow_amount_i128 = 1000000000000;
+    let mint_amount = to_bigint(&e, deposit_amount_i128 + steps as i128);
+    e.ledger().set(LedgerInfo {
+        timestamp: 12345,
+        protocol_version: 1,
+        sequence_number: 10,
+        network_passphrase: Default::default(),
+        base_reserve: 10,
+    });
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(500000));
+
+    // setup env
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &mint_amount,
+    );
+    token_client.with_source_account(&user1_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &mint_amount
+    );
+    pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &deposit_amount_i128);
+
+    // borrow against own collateral
+    let expiration = e.ledger().timestamp() + 100;
+    pool_client.with_source_account(&user1_acct).borrow_obo(&Signature::Invoker, &token_contract_id, &borrow_amount_i128, &expiration);
+
+    for i in 1..=steps {
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345 + i * step,
+            protocol_version: 1,
+            sequence_number: 10 + i as u32,
+            network_passphrase: Default::default(),
+            base_reserve: 10,
+        });
+        pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &1);
+    }
+    pool_client.liability(&token_contract_id, &user1_id)
+}
+
+#[test]
+fn test_interest_short_intervals() {
+    // 600 seconds at 5% accrue the same interest whether the reserve is touched once or every 5 seconds
+    let once = liability_after_steps(1, 600);
+    let often = liability_after_steps(120, 5);
+    assert_eq!(once, 1000000951000);
+    assert_eq!(often, once);
+}
+
+#[test]
+fn test_interest_fixed_rate() {
+    // 10% annual rate
+    let liability = liability_after_one_year(reserve_config(1000000), 2000000, 1000000);
+    assert_eq!(liability, 1100000);
+}
+
+#[test]
+fn test_interest_linear_rate() {
+    // 2% base rate + 20% at full utilization, 50% utilization
+    let rate_model = RateModel::Linear(LinearRate { base: 200000, slope: 2000000 });
+    let config = ReserveConfig { rate_model };
+    let liability = liability_after_one_year(config, 2000000, 1000000);
+    assert_eq!(liability, 1120000);
+}
+
+#[test]
+fn test_interest_kinked_rate() {
+    // 10% slope until 80% utilization, 50% utilization
+    let rate_model = RateModel::Kinked(KinkedRate { base: 0, slope_1: 1000000, slope_2: 10000000, kink: 8000000 });
+    let config = ReserveConfig { rate_model };
+    let liability = liability_after_one_year(config, 2000000, 1000000);
+    assert_eq!(liability, 1062500);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(12))")]
+fn test_interest_invalid_rate_model() {
+    let e = Env::default();
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(-1));
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(12))")]
+fn test_interest_rate_model_above_max_rate() {
+    let e = Env::default();
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+
+    // a slope past the maximum rate would overflow the borrow index
+    let config = ReserveConfig {
+        rate_model: RateModel::Kinked(KinkedRate { base: 0, slope_1: 40000000, slope_2: 1000000000, kink: 8000000 }),
+    };
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &config);
+}
@@ -0,0 +1,163 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, Env, testutils::{Accounts, Ledger, LedgerInfo}};
+use soroban_auth::{Identifier, Signature};
+use soroban_auth_pool::pool::PoolConfig;
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, mint_and_deposit, to_bigint};
+
+const ONE_YEAR: u64 = 31536000;
+
+fn set_timestamp(e: &Env, timestamp: u64) {
+    e.ledger().set(LedgerInfo {
+        timestamp,
+        protocol_version: 1,
+        sequence_number: 10,
+        network_passphrase: Default::default(),
+        base_reserve: 10,
+    });
+}
+
+#[test]
+fn test_liquidate_happy_path() {
+    let e = Env::default();
+    set_timestamp(&e, 12345);
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool with a 10% fixed rate
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(1000000));
+
+    // setup env
+    let lender_acct = e.accounts().generate_and_create();
+    mint_and_deposit(&e, &token_contract_id, &token_client, &token_admin, &pool_client, &pool_id, &lender_acct, 2000000);
+
+    let borrower_acct = e.accounts().generate_and_create();
+    let borrower_id = Identifier::Account(borrower_acct.clone());
+    mint_and_deposit(&e, &token_contract_id, &token_client, &token_admin, &pool_client, &pool_id, &borrower_acct, 1000000);
+    let expiration = e.ledger().timestamp() + 100;
+    pool_client.with_source_account(&borrower_acct).borrow_obo(&Signature::Invoker, &token_contract_id, &1000000, &expiration);
+
+    // interest pushes the liability of the borrower past their collateral
+    set_timestamp(&e, 12345 + ONE_YEAR);
+    assert_eq!(pool_client.liability(&token_contract_id, &borrower_id), 1100000);
+    assert_eq!(pool_client.collateral(&token_contract_id, &borrower_id), 1033333);
+
+    // liquidate
+    let liquidator_acct = e.accounts().generate_and_create();
+    let liquidator_id = Identifier::Account(liquidator_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &liquidator_id,
+        &to_bigint(&e, 500000),
+    );
+    token_client.with_source_account(&liquidator_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &to_bigint(&e, 500000)
+    );
+    pool_client.with_source_account(&liquidator_acct).liquidate(&borrower_id, &token_contract_id, &token_contract_id, &500000);
+
+    assert_eq!(token_client.balance(&liquidator_id), BigInt::zero(&e));
+    assert_eq!(pool_client.liability(&token_contract_id, &borrower_id), 600001);
+    assert_eq!(pool_client.shares(&token_contract_id, &borrower_id), 1000000 - 508065);
+    assert_eq!(pool_client.shares(&token_contract_id, &liquidator_id), 508065);
+    assert_eq!(pool_client.collateral(&token_contract_id, &liquidator_id), 525000);
+}
+
+#[test]
+fn test_liquidate_before_bad_debt() {
+    let e = Env::default();
+    set_timestamp(&e, 12345);
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool with a 75% maximum loan-to-value and a 10% fixed rate
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    let config = PoolConfig { max_ltv: 7500000, ..pool_config() };
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &config);
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(1000000));
+
+    // setup env
+    let lender_acct = e.accounts().generate_and_create();
+    mint_and_deposit(&e, &token_contract_id, &token_client, &token_admin, &pool_client, &pool_id, &lender_acct, 2000000);
+
+    let borrower_acct = e.accounts().generate_and_create();
+    let borrower_id = Identifier::Account(borrower_acct.clone());
+    mint_and_deposit(&e, &token_contract_id, &token_client, &token_admin, &pool_client, &pool_id, &borrower_acct, 1000000);
+    let expiration = e.ledger().timestamp() + 100;
+    pool_client.with_source_account(&borrower_acct).borrow_obo(&Signature::Invoker, &token_contract_id, &750000, &expiration);
+
+    // interest pushes the borrower past the maximum loan-to-value while they are still solvent
+    set_timestamp(&e, 12345 + ONE_YEAR);
+    assert_eq!(pool_client.liability(&token_contract_id, &borrower_id), 825000);
+    assert_eq!(pool_client.collateral(&token_contract_id, &borrower_id), 1025000);
+    assert_eq!(pool_client.health(&borrower_id), 9318181);
+
+    // liquidate
+    let liquidator_acct = e.accounts().generate_and_create();
+    let liquidator_id = Identifier::Account(liquidator_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &liquidator_id,
+        &to_bigint(&e, 400000),
+    );
+    token_client.with_source_account(&liquidator_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &to_bigint(&e, 400000)
+    );
+    pool_client.with_source_account(&liquidator_acct).liquidate(&borrower_id, &token_contract_id, &token_contract_id, &400000);
+
+    // the borrower is left healthy rather than deeper underwater
+    assert_eq!(pool_client.collateral(&token_contract_id, &liquidator_id), 420000);
+    assert_eq!(pool_client.collateral(&token_contract_id, &borrower_id), 604999);
+    assert!(pool_client.health(&borrower_id) > 10000000);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(14))")]
+fn test_liquidate_healthy_borrower() {
+    let e = Env::default();
+    set_timestamp(&e, 12345);
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool with a 10% fixed rate
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(1000000));
+
+    // setup env
+    let borrower_acct = e.accounts().generate_and_create();
+    let borrower_id = Identifier::Account(borrower_acct.clone());
+    mint_and_deposit(&e, &token_contract_id, &token_client, &token_admin, &pool_client, &pool_id, &borrower_acct, 1000000);
+    let expiration = e.ledger().timestamp() + 100;
+    pool_client.with_source_account(&borrower_acct).borrow_obo(&Signature::Invoker, &token_contract_id, &500000, &expiration);
+
+    // liquidate
+    let liquidator_acct = e.accounts().generate_and_create();
+    pool_client.with_source_account(&liquidator_acct).liquidate(&borrower_id, &token_contract_id, &token_contract_id, &100000);
+}
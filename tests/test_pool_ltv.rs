@@ -0,0 +1,186 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, Env, testutils::Accounts};
+use soroban_auth::{Identifier, Signature};
+use soroban_auth_pool::pool::PoolConfig;
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, mint_and_deposit, to_bigint};
+
+#[test]
+fn test_ltv_happy_path() {
+    let e = Env::default();
+    let deposit_amount_i128 = 1000000;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool with a 75% max loan-to-value
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    let config = PoolConfig { max_ltv: 7500000, ..pool_config() };
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &config);
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+    token_client.with_source_account(&user1_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &deposit_amount_i128);
+    assert_eq!(pool_client.health(&user1_id), i128::MAX);
+
+    // borrow up to the maximum loan-to-value
+    let expiration = e.ledger().timestamp() + 100;
+    pool_client.with_source_account(&user1_acct).borrow_obo(&Signature::Invoker, &token_contract_id, &750000, &expiration);
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), 750000);
+    assert_eq!(pool_client.health(&user1_id), 10000000);
+
+    // the admin lowers the maximum loan-to-value
+    pool_client.with_source_account(&token_admin).set_ltv(&Signature::Invoker, &5000000);
+    assert_eq!(pool_client.get_config().max_ltv, 5000000);
+    assert_eq!(pool_client.health(&user1_id), 6666666);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(8))")]
+fn test_ltv_borrow_above_max_ltv() {
+    let e = Env::default();
+    let deposit_amount_i128 = 1000000;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool with a 75% max loan-to-value
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    let config = PoolConfig { max_ltv: 7500000, ..pool_config() };
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &config);
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+    token_client.with_source_account(&user1_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &deposit_amount_i128);
+
+    // borrow past the maximum loan-to-value
+    let expiration = e.ledger().timestamp() + 100;
+    pool_client.with_source_account(&user1_acct).borrow_obo(&Signature::Invoker, &token_contract_id, &750001, &expiration);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(15))")]
+fn test_ltv_set_by_non_admin() {
+    let e = Env::default();
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    let user1_acct = e.accounts().generate_and_create();
+    pool_client.with_source_account(&user1_acct).set_ltv(&Signature::Invoker, &5000000);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(13))")]
+fn test_ltv_invalid_at_initialize() {
+    let e = Env::default();
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool with no borrowing power
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    let config = PoolConfig { max_ltv: 0, ..pool_config() };
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &config);
+}
+
+#[test]
+fn test_ltv_lowered_makes_borrower_liquidatable() {
+    let e = Env::default();
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool with a 75% maximum loan-to-value
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    let config = PoolConfig { max_ltv: 7500000, ..pool_config() };
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &config);
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let borrower_acct = e.accounts().generate_and_create();
+    let borrower_id = Identifier::Account(borrower_acct.clone());
+    mint_and_deposit(&e, &token_contract_id, &token_client, &token_admin, &pool_client, &pool_id, &borrower_acct, 1000000);
+    let expiration = e.ledger().timestamp() + 100;
+    pool_client.with_source_account(&borrower_acct).borrow_obo(&Signature::Invoker, &token_contract_id, &500000, &expiration);
+
+    // lowering the maximum loan-to-value leaves the borrower below the health threshold
+    pool_client.with_source_account(&token_admin).set_ltv(&Signature::Invoker, &4000000);
+    assert_eq!(pool_client.health(&borrower_id), 8000000);
+
+    // liquidate
+    let liquidator_acct = e.accounts().generate_and_create();
+    let liquidator_id = Identifier::Account(liquidator_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &liquidator_id,
+        &to_bigint(&e, 100000),
+    );
+    token_client.with_source_account(&liquidator_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &to_bigint(&e, 100000)
+    );
+    pool_client.with_source_account(&liquidator_acct).liquidate(&borrower_id, &token_contract_id, &token_contract_id, &100000);
+
+    assert_eq!(pool_client.liability(&token_contract_id, &borrower_id), 400000);
+    assert_eq!(pool_client.collateral(&token_contract_id, &liquidator_id), 105000);
+    assert_eq!(pool_client.collateral(&token_contract_id, &borrower_id), 895000);
+}
@@ -0,0 +1,162 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, Env, testutils::Accounts, symbol};
+use soroban_auth::{Identifier, Signature, testutils::ed25519};
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, create_oracle_contract, pool_config, reserve_config, generate_contract_id, to_bigint};
+
+#[test]
+fn test_multi_asset_borrow_against_other_reserve() {
+    let e = Env::default();
+    let deposit_amount_i128 = 1000000;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let borrow_amount_i128 = 400000;
+
+    // deploy token contracts
+    let token_admin = e.accounts().generate_and_create();
+    let token_a_contract_id = generate_contract_id(&e);
+    let token_a_client = create_token_contract(&e, &token_a_contract_id, &token_admin);
+    let token_b_contract_id = generate_contract_id(&e);
+    let token_b_client = create_token_contract(&e, &token_b_contract_id, &token_admin);
+
+    // deploy and init auth pool with a reserve for each token
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_a_contract_id, &reserve_config(0));
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_b_contract_id, &reserve_config(0));
+
+    // price both tokens equally
+    let oracle_contract_id = generate_contract_id(&e);
+    let oracle_client = create_oracle_contract(&e, &oracle_contract_id);
+    oracle_client.set_price(&token_a_contract_id, &10000000, &0);
+    oracle_client.set_price(&token_b_contract_id, &10000000, &0);
+    pool_client.with_source_account(&token_admin).set_oracle(&Signature::Invoker, &oracle_contract_id, &3600);
+
+    // setup env
+    let lender_acct = e.accounts().generate_and_create();
+    let lender_id = Identifier::Account(lender_acct.clone());
+    token_b_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &lender_id,
+        &deposit_amount,
+    );
+    token_b_client.with_source_account(&lender_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&lender_acct).deposit(&token_b_contract_id, &deposit_amount_i128);
+
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_a_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+
+    // deposit token A with permit
+    let user1_token_nonce = token_a_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_a_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_a_contract_id, &deposit_amount_i128);
+
+    // borrow token B on behalf of user1 against their token A collateral
+    let signer_nonce = pool_client.nonce(&user1_id);
+    let expiration = e.ledger().timestamp() + 100;
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_obo"),
+        (&user1_id, &signer_nonce, &user2_id, &token_b_contract_id, &borrow_amount_i128, &expiration),
+    );
+    pool_client.with_source_account(&user2_acct).borrow_obo(&sig, &token_b_contract_id, &borrow_amount_i128, &expiration);
+
+    assert_eq!(token_b_client.balance(&user2_id), to_bigint(&e, borrow_amount_i128));
+    assert_eq!(pool_client.collateral(&token_a_contract_id, &user1_id), deposit_amount_i128);
+    assert_eq!(pool_client.liability(&token_a_contract_id, &user1_id), 0);
+    assert_eq!(pool_client.liability(&token_b_contract_id, &user1_id), borrow_amount_i128);
+    assert_eq!(pool_client.health(&user1_id), 25000000);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(8))")]
+fn test_multi_asset_borrow_more_than_collateral() {
+    let e = Env::default();
+    let deposit_amount_i128 = 1000000;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let lend_amount = to_bigint(&e, 2 * deposit_amount_i128);
+
+    // deploy token contracts
+    let token_admin = e.accounts().generate_and_create();
+    let token_a_contract_id = generate_contract_id(&e);
+    let token_a_client = create_token_contract(&e, &token_a_contract_id, &token_admin);
+    let token_b_contract_id = generate_contract_id(&e);
+    let token_b_client = create_token_contract(&e, &token_b_contract_id, &token_admin);
+
+    // deploy and init auth pool with a reserve for each token
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_a_contract_id, &reserve_config(0));
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_b_contract_id, &reserve_config(0));
+
+    // price both tokens equally
+    let oracle_contract_id = generate_contract_id(&e);
+    let oracle_client = create_oracle_contract(&e, &oracle_contract_id);
+    oracle_client.set_price(&token_a_contract_id, &10000000, &0);
+    oracle_client.set_price(&token_b_contract_id, &10000000, &0);
+    pool_client.with_source_account(&token_admin).set_oracle(&Signature::Invoker, &oracle_contract_id, &3600);
+
+    // setup env
+    let lender_acct = e.accounts().generate_and_create();
+    let lender_id = Identifier::Account(lender_acct.clone());
+    token_b_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &lender_id,
+        &lend_amount,
+    );
+    token_b_client.with_source_account(&lender_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &lend_amount
+    );
+    pool_client.with_source_account(&lender_acct).deposit(&token_b_contract_id, &(2 * deposit_amount_i128));
+
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    token_a_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+    token_a_client.with_source_account(&user1_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&user1_acct).deposit(&token_a_contract_id, &deposit_amount_i128);
+
+    // borrow more token B than the token A collateral is worth
+    let expiration = e.ledger().timestamp() + 100;
+    pool_client.with_source_account(&user1_acct).borrow_obo(&Signature::Invoker, &token_b_contract_id, &(deposit_amount_i128 + 1), &expiration);
+}
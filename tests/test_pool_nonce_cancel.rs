@@ -0,0 +1,230 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, Env, IntoVal, testutils::Accounts, symbol, vec};
+use soroban_auth::{Identifier, Signature, testutils::ed25519};
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, pool_events, to_bigint};
+
+#[test]
+#[should_panic(expected = "Status(ContractError(2))")]
+fn test_nonce_cancel_outstanding_signatures() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let borrow_amount_i128 = 1000;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    // hand out a borrow signature over the nonce after the current one
+    let signer_nonce = pool_client.nonce(&user1_id);
+    let voucher_nonce = signer_nonce + 1;
+    let expiration = e.ledger().timestamp() + 100;
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_obo"),
+        (&user1_id, &voucher_nonce, &user2_id, &token_contract_id, &borrow_amount_i128, &expiration),
+    );
+
+    // cancel it before it is used
+    let cancel_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("cncl_nonce"),
+        (&user1_id, &signer_nonce, &voucher_nonce),
+    );
+    pool_client.cncl_nonce(&cancel_sig, &voucher_nonce);
+    assert_eq!(pool_client.nonce(&user1_id), voucher_nonce + 1);
+
+    pool_client.with_source_account(&user2_acct).borrow_obo(&sig, &token_contract_id, &borrow_amount_i128, &expiration);
+}
+
+#[test]
+fn test_nonce_bump() {
+    let e = Env::default();
+
+    // deploy auth pool
+    let token_admin = e.accounts().generate_and_create();
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+
+    // skip the next 5 nonces after the one used to sign the bump
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    let signer_nonce = pool_client.nonce(&user1_id);
+    let n: i64 = 5;
+    let bump_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("bump_nonce"),
+        (&user1_id, &signer_nonce, &n),
+    );
+    pool_client.bump_nonce(&bump_sig, &n);
+
+    assert_eq!(pool_client.nonce(&user1_id), signer_nonce + 1 + n);
+    assert_eq!(
+        pool_events(&e, &pool_contract_id),
+        vec![
+            &e,
+            (
+                pool_contract_id.clone(),
+                (symbol!("nonce"), user1_id.clone()).into_val(&e),
+                signer_nonce.into_val(&e)
+            ),
+            (
+                pool_contract_id.clone(),
+                (symbol!("nonce_bump"), user1_id.clone()).into_val(&e),
+                (n, signer_nonce + 1 + n).into_val(&e)
+            ),
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(4))")]
+fn test_nonce_bump_not_positive() {
+    let e = Env::default();
+
+    // deploy auth pool
+    let token_admin = e.accounts().generate_and_create();
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+
+    let user1_acct = e.accounts().generate_and_create();
+    pool_client.with_source_account(&user1_acct).bump_nonce(&Signature::Invoker, &0);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(4))")]
+fn test_nonce_cancel_last_nonce() {
+    let e = Env::default();
+
+    // deploy auth pool
+    let token_admin = e.accounts().generate_and_create();
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+
+    // cancelling up to the last nonce would leave no nonce to sign with
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    let signer_nonce = pool_client.nonce(&user1_id);
+    let cancel: i64 = i64::MAX - 1;
+    let cancel_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("cncl_nonce"),
+        (&user1_id, &signer_nonce, &cancel),
+    );
+    pool_client.cncl_nonce(&cancel_sig, &cancel);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(4))")]
+fn test_nonce_bump_past_last_nonce() {
+    let e = Env::default();
+
+    // deploy auth pool
+    let token_admin = e.accounts().generate_and_create();
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    let signer_nonce = pool_client.nonce(&user1_id);
+    let n: i64 = i64::MAX;
+    let bump_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("bump_nonce"),
+        (&user1_id, &signer_nonce, &n),
+    );
+    pool_client.bump_nonce(&bump_sig, &n);
+}
+
+#[test]
+fn test_nonce_bump_by_invoker() {
+    let e = Env::default();
+
+    // deploy auth pool
+    let token_admin = e.accounts().generate_and_create();
+    let token_admin_id = Identifier::Account(token_admin.clone());
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&token_admin_id, &pool_config());
+
+    // the admin skips a nonce without signing
+    pool_client.with_source_account(&token_admin).bump_nonce(&Signature::Invoker, &1);
+    assert_eq!(pool_client.nonce(&token_admin_id), 1);
+
+    // the admin can still act as the invoker
+    pool_client.with_source_account(&token_admin).set_ltv(&Signature::Invoker, &5000000);
+    assert_eq!(pool_client.get_config().max_ltv, 5000000);
+}
+
+#[test]
+fn test_nonce_cancel_by_invoker() {
+    let e = Env::default();
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    let user2_id = Identifier::Account(e.accounts().generate_and_create());
+
+    // the user cancels their outstanding signatures without signing
+    pool_client.with_source_account(&user1_acct).cncl_nonce(&Signature::Invoker, &4);
+    assert_eq!(pool_client.nonce(&user1_id), 5);
+
+    // the user can still act as the invoker
+    pool_client.with_source_account(&user1_acct).approve_b(&Signature::Invoker, &user2_id, &token_contract_id, &1000);
+    assert_eq!(pool_client.allowance(&user1_id, &user2_id, &token_contract_id), 1000);
+}
@@ -0,0 +1,206 @@
+#![cfg(test)]
+
+use soroban_sdk::{Env, testutils::{Accounts, Ledger, LedgerInfo}};
+use soroban_auth::{Identifier, Signature};
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, create_oracle_contract, pool_config, reserve_config, generate_contract_id, mint_and_deposit, to_bigint};
+
+#[test]
+fn test_oracle_borrow_against_priced_collateral() {
+    let e = Env::default();
+
+    // deploy token contracts
+    let token_admin = e.accounts().generate_and_create();
+    let token_a_contract_id = generate_contract_id(&e);
+    let token_a_client = create_token_contract(&e, &token_a_contract_id, &token_admin);
+    let token_b_contract_id = generate_contract_id(&e);
+    let token_b_client = create_token_contract(&e, &token_b_contract_id, &token_admin);
+
+    // deploy oracle contract, token A is worth twice token B
+    let oracle_contract_id = generate_contract_id(&e);
+    let oracle_client = create_oracle_contract(&e, &oracle_contract_id);
+    oracle_client.set_price(&token_a_contract_id, &20000000, &0);
+    oracle_client.set_price(&token_b_contract_id, &10000000, &0);
+
+    // deploy and init auth pool with a reserve for each token
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_a_contract_id, &reserve_config(0));
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_b_contract_id, &reserve_config(0));
+    pool_client.with_source_account(&token_admin).set_oracle(&Signature::Invoker, &oracle_contract_id, &3600);
+    assert_eq!(pool_client.get_oracle().max_age, 3600);
+
+    // setup env
+    let lender_acct = e.accounts().generate_and_create();
+    mint_and_deposit(&e, &token_b_contract_id, &token_b_client, &token_admin, &pool_client, &pool_id, &lender_acct, 3000000);
+
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    mint_and_deposit(&e, &token_a_contract_id, &token_a_client, &token_admin, &pool_client, &pool_id, &user1_acct, 1000000);
+
+    // borrow more token B than the amount of token A deposited
+    let expiration = e.ledger().timestamp() + 100;
+    pool_client.with_source_account(&user1_acct).borrow_obo(&Signature::Invoker, &token_b_contract_id, &1500000, &expiration);
+
+    assert_eq!(token_b_client.balance(&user1_id), to_bigint(&e, 1500000));
+    assert_eq!(pool_client.liability(&token_b_contract_id, &user1_id), 1500000);
+    assert_eq!(pool_client.health(&user1_id), 13333333);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(19))")]
+fn test_oracle_stale_price() {
+    let e = Env::default();
+    e.ledger().set(LedgerInfo {
+        timestamp: 10000,
+        protocol_version: 1,
+        sequence_number: 10,
+        network_passphrase: Default::default(),
+        base_reserve: 10,
+    });
+
+    // deploy token contracts
+    let token_admin = e.accounts().generate_and_create();
+    let token_a_contract_id = generate_contract_id(&e);
+    let token_a_client = create_token_contract(&e, &token_a_contract_id, &token_admin);
+    let token_b_contract_id = generate_contract_id(&e);
+    let token_b_client = create_token_contract(&e, &token_b_contract_id, &token_admin);
+
+    // deploy oracle contract with a price for token A older than the staleness limit
+    let oracle_contract_id = generate_contract_id(&e);
+    let oracle_client = create_oracle_contract(&e, &oracle_contract_id);
+    oracle_client.set_price(&token_a_contract_id, &20000000, &5000);
+    oracle_client.set_price(&token_b_contract_id, &10000000, &10000);
+
+    // deploy and init auth pool with a reserve for each token
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_a_contract_id, &reserve_config(0));
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_b_contract_id, &reserve_config(0));
+    pool_client.with_source_account(&token_admin).set_oracle(&Signature::Invoker, &oracle_contract_id, &3600);
+
+    // setup env
+    let lender_acct = e.accounts().generate_and_create();
+    mint_and_deposit(&e, &token_b_contract_id, &token_b_client, &token_admin, &pool_client, &pool_id, &lender_acct, 3000000);
+
+    let user1_acct = e.accounts().generate_and_create();
+    mint_and_deposit(&e, &token_a_contract_id, &token_a_client, &token_admin, &pool_client, &pool_id, &user1_acct, 1000000);
+
+    // borrow against collateral in another reserve
+    let expiration = e.ledger().timestamp() + 100;
+    pool_client.with_source_account(&user1_acct).borrow_obo(&Signature::Invoker, &token_b_contract_id, &100000, &expiration);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(18))")]
+fn test_oracle_not_set() {
+    let e = Env::default();
+
+    // deploy token contracts
+    let token_admin = e.accounts().generate_and_create();
+    let token_a_contract_id = generate_contract_id(&e);
+    let token_a_client = create_token_contract(&e, &token_a_contract_id, &token_admin);
+    let token_b_contract_id = generate_contract_id(&e);
+    let token_b_client = create_token_contract(&e, &token_b_contract_id, &token_admin);
+
+    // deploy and init auth pool with a reserve for each token and no oracle
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_a_contract_id, &reserve_config(0));
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_b_contract_id, &reserve_config(0));
+
+    // setup env
+    let lender_acct = e.accounts().generate_and_create();
+    mint_and_deposit(&e, &token_b_contract_id, &token_b_client, &token_admin, &pool_client, &pool_id, &lender_acct, 3000000);
+
+    let user1_acct = e.accounts().generate_and_create();
+    mint_and_deposit(&e, &token_a_contract_id, &token_a_client, &token_admin, &pool_client, &pool_id, &user1_acct, 1000000);
+
+    // borrow against collateral in another reserve
+    let expiration = e.ledger().timestamp() + 100;
+    pool_client.with_source_account(&user1_acct).borrow_obo(&Signature::Invoker, &token_b_contract_id, &100000, &expiration);
+}
+
+#[test]
+fn test_oracle_not_needed_without_liabilities() {
+    let e = Env::default();
+
+    // deploy token contracts
+    let token_admin = e.accounts().generate_and_create();
+    let token_a_contract_id = generate_contract_id(&e);
+    let token_a_client = create_token_contract(&e, &token_a_contract_id, &token_admin);
+    let token_b_contract_id = generate_contract_id(&e);
+    let token_b_client = create_token_contract(&e, &token_b_contract_id, &token_admin);
+
+    // deploy and init auth pool with a reserve for each token and no oracle
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_a_contract_id, &reserve_config(0));
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_b_contract_id, &reserve_config(0));
+
+    // deposit in both reserves without borrowing
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    mint_and_deposit(&e, &token_a_contract_id, &token_a_client, &token_admin, &pool_client, &pool_id, &user1_acct, 1000000);
+    mint_and_deposit(&e, &token_b_contract_id, &token_b_client, &token_admin, &pool_client, &pool_id, &user1_acct, 1000000);
+
+    // withdrawing doesn't need any prices
+    pool_client.with_source_account(&user1_acct).withdraw(&token_a_contract_id, &400000);
+
+    assert_eq!(token_a_client.balance(&user1_id), to_bigint(&e, 400000));
+    assert_eq!(pool_client.collateral(&token_a_contract_id, &user1_id), 600000);
+    assert_eq!(pool_client.collateral(&token_b_contract_id, &user1_id), 1000000);
+    assert_eq!(pool_client.health(&user1_id), i128::MAX);
+}
+
+#[test]
+fn test_oracle_values_beyond_max_amount() {
+    let e = Env::default();
+    let deposit_amount_i128 = 5000000000000000000;
+
+    // deploy token contracts
+    let token_admin = e.accounts().generate_and_create();
+    let token_a_contract_id = generate_contract_id(&e);
+    let token_a_client = create_token_contract(&e, &token_a_contract_id, &token_admin);
+    let token_b_contract_id = generate_contract_id(&e);
+    let token_b_client = create_token_contract(&e, &token_b_contract_id, &token_admin);
+
+    // deploy oracle contract, token A is worth 100 times token B
+    let oracle_contract_id = generate_contract_id(&e);
+    let oracle_client = create_oracle_contract(&e, &oracle_contract_id);
+    oracle_client.set_price(&token_a_contract_id, &1000000000, &0);
+    oracle_client.set_price(&token_b_contract_id, &10000000, &0);
+
+    // deploy and init auth pool with a reserve for each token
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_a_contract_id, &reserve_config(0));
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_b_contract_id, &reserve_config(0));
+    pool_client.with_source_account(&token_admin).set_oracle(&Signature::Invoker, &oracle_contract_id, &3600);
+
+    // setup env
+    let lender_acct = e.accounts().generate_and_create();
+    mint_and_deposit(&e, &token_b_contract_id, &token_b_client, &token_admin, &pool_client, &pool_id, &lender_acct, 1000000);
+
+    // the collateral of user1 is worth more than the largest token amount the pool accounts for
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    mint_and_deposit(&e, &token_a_contract_id, &token_a_client, &token_admin, &pool_client, &pool_id, &user1_acct, deposit_amount_i128);
+
+    let expiration = e.ledger().timestamp() + 100;
+    pool_client.with_source_account(&user1_acct).borrow_obo(&Signature::Invoker, &token_b_contract_id, &1000, &expiration);
+
+    assert_eq!(pool_client.liability(&token_b_contract_id, &user1_id), 1000);
+    assert_eq!(pool_client.health(&user1_id), 5000000000000000000000000);
+}
@@ -0,0 +1,90 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, Env, testutils::Accounts, symbol};
+use soroban_auth::{Identifier, Signature, testutils::ed25519};
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, to_bigint};
+
+/// TODO: Test support for accounts
+///       blocked by: https://github.com/stellar/rs-soroban-sdk/issues/741
+
+#[test]
+fn test_repay_permit_happy_path() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let borrow_amount_i128 = 23456789;
+    let borrow_amount = to_bigint(&e, borrow_amount_i128);
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+
+    let (user3_id, user3_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user3_id,
+        &borrow_amount,
+    );
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    // borrow on behalf of
+    let signer_nonce = pool_client.nonce(&user1_id);
+    let expiration = e.ledger().timestamp() + 100;
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_obo"),
+        (&user1_id, &signer_nonce, &user2_id, &token_contract_id, &borrow_amount_i128, &expiration),
+    );
+    pool_client.with_source_account(&user2_acct).borrow_obo(&sig, &token_contract_id, &borrow_amount_i128, &expiration);
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), borrow_amount_i128);
+
+    // repay with permit on behalf of user1
+    let user3_token_nonce = token_client.nonce(&user3_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user3_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user3_id, &user3_token_nonce, &pool_id, &borrow_amount),
+    );
+    pool_client.repay_p(&approval_sig, &user1_id, &token_contract_id, &borrow_amount_i128);
+
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), 0);
+    assert_eq!(token_client.balance(&user3_id), BigInt::zero(&e));
+    assert_eq!(token_client.balance(&pool_id), deposit_amount);
+}
@@ -0,0 +1,281 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, BytesN, Env, testutils::Accounts, symbol, vec};
+use soroban_auth::{Identifier, Signature, testutils::ed25519};
+use soroban_auth_pool::pool::SessionScope;
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, to_bigint};
+
+fn session_pubkey(id: &Identifier) -> BytesN<32> {
+    match id {
+        Identifier::Ed25519(pubkey) => pubkey.clone(),
+        _ => panic!("not an ed25519 identifier"),
+    }
+}
+
+#[test]
+fn test_session_keys_happy_path() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let borrow_amount_i128 = 1000;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+    token_client.with_source_account(&user1_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &deposit_amount_i128);
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+
+    // register a session key once, co-signed by the session key
+    let (session_id, session_sign) = ed25519::generate(&e);
+    let expiration = e.ledger().timestamp() + 100;
+    let scope = SessionScope {
+        token: token_contract_id.clone(),
+        max_amount: 3000,
+        expiration,
+        receivers: vec![&e, user2_id.clone()],
+    };
+    let session_nonce = pool_client.nonce(&session_id);
+    let session_sig = ed25519::sign(
+        &e,
+        &session_sign,
+        &pool_contract_id,
+        symbol!("add_sess"),
+        (&session_id, &session_nonce, &user1_id, &scope),
+    );
+    pool_client.with_source_account(&user1_acct).add_sess(&Signature::Invoker, &session_sig, &scope);
+
+    // the session key signs for user2 to borrow on-behalf-of user1
+    let session_nonce = pool_client.nonce(&session_id);
+    let sig = ed25519::sign(
+        &e,
+        &session_sign,
+        &pool_contract_id,
+        symbol!("borrow_ses"),
+        (&session_id, &session_nonce, &user1_id, &user2_id, &token_contract_id, &borrow_amount_i128, &expiration),
+    );
+    pool_client.with_source_account(&user2_acct).borrow_ses(&sig, &user1_id, &token_contract_id, &borrow_amount_i128, &expiration);
+
+    assert_eq!(token_client.balance(&user2_id), to_bigint(&e, borrow_amount_i128));
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), borrow_amount_i128);
+    assert_eq!(pool_client.liability(&token_contract_id, &session_id), 0);
+    assert_eq!(pool_client.sess_left(&user1_id, &session_pubkey(&session_id)), 2000);
+
+    // revoking the session key leaves nothing to borrow
+    pool_client.with_source_account(&user1_acct).rm_sess(&Signature::Invoker, &session_pubkey(&session_id));
+    assert_eq!(pool_client.sess_left(&user1_id, &session_pubkey(&session_id)), 0);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(15))")]
+fn test_session_keys_receiver_not_allowed() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let borrow_amount_i128 = 1000;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+    token_client.with_source_account(&user1_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &deposit_amount_i128);
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+    let user3_acct = e.accounts().generate_and_create();
+    let user3_id = Identifier::Account(user3_acct.clone());
+
+    // register a session key that can only borrow for user2
+    let (session_id, session_sign) = ed25519::generate(&e);
+    let expiration = e.ledger().timestamp() + 100;
+    let scope = SessionScope {
+        token: token_contract_id.clone(),
+        max_amount: 3000,
+        expiration,
+        receivers: vec![&e, user2_id],
+    };
+    let session_nonce = pool_client.nonce(&session_id);
+    let session_sig = ed25519::sign(
+        &e,
+        &session_sign,
+        &pool_contract_id,
+        symbol!("add_sess"),
+        (&session_id, &session_nonce, &user1_id, &scope),
+    );
+    pool_client.with_source_account(&user1_acct).add_sess(&Signature::Invoker, &session_sig, &scope);
+
+    // the session key signs for user3 instead
+    let session_nonce = pool_client.nonce(&session_id);
+    let sig = ed25519::sign(
+        &e,
+        &session_sign,
+        &pool_contract_id,
+        symbol!("borrow_ses"),
+        (&session_id, &session_nonce, &user1_id, &user3_id, &token_contract_id, &borrow_amount_i128, &expiration),
+    );
+    pool_client.with_source_account(&user3_acct).borrow_ses(&sig, &user1_id, &token_contract_id, &borrow_amount_i128, &expiration);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(27))")]
+fn test_session_keys_max_amount_exceeded() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let borrow_amount_i128 = 2000;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+    token_client.with_source_account(&user1_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &deposit_amount_i128);
+
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+
+    let (session_id, session_sign) = ed25519::generate(&e);
+    let expiration = e.ledger().timestamp() + 100;
+    let scope = SessionScope {
+        token: token_contract_id.clone(),
+        max_amount: 3000,
+        expiration,
+        receivers: vec![&e, user2_id.clone()],
+    };
+    let session_nonce = pool_client.nonce(&session_id);
+    let session_sig = ed25519::sign(
+        &e,
+        &session_sign,
+        &pool_contract_id,
+        symbol!("add_sess"),
+        (&session_id, &session_nonce, &user1_id, &scope),
+    );
+    pool_client.with_source_account(&user1_acct).add_sess(&Signature::Invoker, &session_sig, &scope);
+
+    // the second borrow goes past the maximum amount of the session key
+    for _ in 0..2 {
+        let session_nonce = pool_client.nonce(&session_id);
+        let sig = ed25519::sign(
+            &e,
+            &session_sign,
+            &pool_contract_id,
+            symbol!("borrow_ses"),
+            (&session_id, &session_nonce, &user1_id, &user2_id, &token_contract_id, &borrow_amount_i128, &expiration),
+        );
+        pool_client.with_source_account(&user2_acct).borrow_ses(&sig, &user1_id, &token_contract_id, &borrow_amount_i128, &expiration);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Failed ED25519 verification")]
+fn test_session_keys_registered_by_third_party() {
+    let e = Env::default();
+    let borrow_amount_i128 = 1000;
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+
+    // user2 gets hold of a signature user1 made for something else
+    let expiration = e.ledger().timestamp() + 100;
+    let user1_nonce = pool_client.nonce(&user1_id);
+    let user1_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("borrow_obo"),
+        (&user1_id, &user1_nonce, &user2_id, &token_contract_id, &borrow_amount_i128, &expiration),
+    );
+
+    // and tries to register the key of user1 as their own session key with it
+    let scope = SessionScope {
+        token: token_contract_id.clone(),
+        max_amount: 3000,
+        expiration,
+        receivers: vec![&e, user2_id],
+    };
+    pool_client.with_source_account(&user2_acct).add_sess(&Signature::Invoker, &user1_sig, &scope);
+}
@@ -0,0 +1,93 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, Env, testutils::{Accounts, Ledger, LedgerInfo}};
+use soroban_auth::{Identifier, Signature};
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, to_bigint};
+
+const ONE_YEAR: u64 = 31536000;
+
+#[test]
+fn test_shares_earn_interest() {
+    let e = Env::default();
+    let deposit_amount_i128 = 2000000;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+    let borrow_amount_i128 = 1000000;
+    e.ledger().set(LedgerInfo {
+        timestamp: 12345,
+        protocol_version: 1,
+        sequence_number: 10,
+        network_passphrase: Default::default(),
+        base_reserve: 10,
+    });
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool with a 10% fixed rate
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(1000000));
+
+    // setup env
+    let user1_acct = e.accounts().generate_and_create();
+    let user1_id = Identifier::Account(user1_acct.clone());
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+    token_client.with_source_account(&user1_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &deposit_amount
+    );
+    pool_client.with_source_account(&user1_acct).deposit(&token_contract_id, &deposit_amount_i128);
+    assert_eq!(pool_client.shares(&token_contract_id, &user1_id), deposit_amount_i128);
+    assert_eq!(pool_client.exch_rate(&token_contract_id), 1_000_000_000);
+
+    // borrow against own collateral
+    let expiration = e.ledger().timestamp() + 100;
+    pool_client.with_source_account(&user1_acct).borrow_obo(&Signature::Invoker, &token_contract_id, &borrow_amount_i128, &expiration);
+
+    // a year of interest accrues to the depositor
+    e.ledger().set(LedgerInfo {
+        timestamp: 12345 + ONE_YEAR,
+        protocol_version: 1,
+        sequence_number: 20,
+        network_passphrase: Default::default(),
+        base_reserve: 10,
+    });
+    assert_eq!(pool_client.liability(&token_contract_id, &user1_id), 1100000);
+    assert_eq!(pool_client.collateral(&token_contract_id, &user1_id), 2100000);
+    assert_eq!(pool_client.exch_rate(&token_contract_id), 1_050_000_000);
+
+    // new deposits receive shares at the current exchange rate
+    let user2_acct = e.accounts().generate_and_create();
+    let user2_id = Identifier::Account(user2_acct.clone());
+    let user2_deposit_i128 = 1050000;
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user2_id,
+        &to_bigint(&e, user2_deposit_i128),
+    );
+    token_client.with_source_account(&user2_acct).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &pool_id,
+        &to_bigint(&e, user2_deposit_i128)
+    );
+    pool_client.with_source_account(&user2_acct).deposit(&token_contract_id, &user2_deposit_i128);
+
+    assert_eq!(pool_client.shares(&token_contract_id, &user2_id), 1000000);
+    assert_eq!(pool_client.collateral(&token_contract_id, &user2_id), user2_deposit_i128);
+    assert_eq!(pool_client.tot_shares(&token_contract_id), 3000000);
+}
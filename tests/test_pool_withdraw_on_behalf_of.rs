@@ -0,0 +1,127 @@
+#![cfg(test)]
+
+use soroban_sdk::{BigInt, Env, testutils::Accounts, symbol};
+use soroban_auth::{Identifier, Signature, testutils::ed25519};
+
+mod helper;
+use helper::{create_token_contract, create_pool_contract, pool_config, reserve_config, generate_contract_id, to_bigint};
+
+/// TODO: Test support for accounts
+///       blocked by: https://github.com/stellar/rs-soroban-sdk/issues/741
+
+#[test]
+fn test_withdraw_on_behalf_of_happy_path() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let bot_acct = e.accounts().generate_and_create();
+    let bot_id = Identifier::Account(bot_acct.clone());
+    let receiver_id = Identifier::Account(e.accounts().generate_and_create());
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    // withdraw on behalf of
+    let signer_nonce = pool_client.nonce(&user1_id);
+    let expiration = e.ledger().timestamp() + 100;
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("withdr_obo"),
+        (&user1_id, &signer_nonce, &receiver_id, &token_contract_id, &deposit_amount_i128, &expiration),
+    );
+    pool_client.with_source_account(&bot_acct).withdr_obo(&sig, &receiver_id, &token_contract_id, &deposit_amount_i128, &expiration);
+
+    assert_eq!(token_client.balance(&receiver_id), deposit_amount);
+    assert_eq!(token_client.balance(&bot_id), BigInt::zero(&e));
+    assert_eq!(token_client.balance(&pool_id), BigInt::zero(&e));
+    assert_eq!(pool_client.collateral(&token_contract_id, &user1_id), 0);
+    assert_eq!(pool_client.nonce(&user1_id), signer_nonce + 1);
+}
+
+#[test]
+#[should_panic(expected = "Failed ED25519 verification")]
+fn test_withdraw_on_behalf_of_wrong_receiver() {
+    let e = Env::default();
+    let deposit_amount_i128 = 123456789;
+    let deposit_amount = to_bigint(&e, deposit_amount_i128);
+
+    // deploy token contract
+    let token_admin = e.accounts().generate_and_create();
+    let token_contract_id = generate_contract_id(&e);
+    let token_client = create_token_contract(&e, &token_contract_id, &token_admin);
+
+    // deploy and init auth pool
+    let pool_contract_id = generate_contract_id(&e);
+    let pool_id = Identifier::Contract(pool_contract_id.clone());
+    let pool_client = create_pool_contract(&e, &pool_contract_id);
+    pool_client.initialize(&Identifier::Account(token_admin.clone()), &pool_config());
+    pool_client.with_source_account(&token_admin).add_res(&Signature::Invoker, &token_contract_id, &reserve_config(0));
+
+    // setup env
+    let (user1_id, user1_sign) = ed25519::generate(&e);
+    token_client.with_source_account(&token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &user1_id,
+        &deposit_amount,
+    );
+
+    let bot_acct = e.accounts().generate_and_create();
+    let bot_id = Identifier::Account(bot_acct.clone());
+    let receiver_id = Identifier::Account(e.accounts().generate_and_create());
+
+    // deposit with permit
+    let user1_token_nonce = token_client.nonce(&user1_id);
+    let approval_sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &token_contract_id,
+        symbol!("approve"),
+        (&user1_id, &user1_token_nonce, &pool_id, &deposit_amount),
+    );
+    pool_client.deposit_p(&approval_sig, &token_contract_id, &deposit_amount_i128);
+
+    // withdraw on behalf of to an address the signer did not choose
+    let signer_nonce = pool_client.nonce(&user1_id);
+    let expiration = e.ledger().timestamp() + 100;
+    let sig = ed25519::sign(
+        &e,
+        &user1_sign,
+        &pool_contract_id,
+        symbol!("withdr_obo"),
+        (&user1_id, &signer_nonce, &receiver_id, &token_contract_id, &deposit_amount_i128, &expiration),
+    );
+    pool_client.with_source_account(&bot_acct).withdr_obo(&sig, &bot_id, &token_contract_id, &deposit_amount_i128, &expiration);
+}